@@ -1,14 +1,18 @@
 use super::*;
 
+use anyhow::Context;
+
+use zfs::zfs::property;
+
 #[derive(Debug, clap::Args)]
 pub struct Create {
     /// Volume size; if specified ZVOL will be created
     #[clap(short = 'V')]
-    volsize: Option<u64>,
+    volsize: Option<property::ByteSize>,
 
     /// Volume block size (equivalent to -o volblocksize=<value>)
     #[clap(short = 'b', requires = "volsize")]
-    volblocksize: Option<u64>,
+    volblocksize: Option<property::ByteSize>,
 
     /// Create a sparse volume with no reservation
     #[clap(short, requires = "volsize")]
@@ -44,18 +48,41 @@ pub struct Create {
 impl Create {
     pub fn exec(self) -> anyhow::Result<String> {
         // println!("{self:?}");
+        let r#type = if self.volsize.is_some() {
+            property::DatasetType::Volume
+        } else {
+            property::DatasetType::Filesystem
+        };
+        for (property, value) in &self.properties {
+            property::registry()
+                .validate(property, value, r#type)
+                .with_context(|| format!("cannot set '{property}={value}'"))?;
+        }
+
         let text = if let Some(size) = self.volsize {
+            let zvol = match self.volblocksize {
+                Some(blocksize) => zfs::Zfs::volume().blocksize(blocksize),
+                None => zfs::Zfs::volume(),
+            };
             self.properties
                 .iter()
-                .fold(zfs::Zfs::volume(), |zvol, (k, v)| zvol.property(k, v))
+                .fold(zvol, |zvol, (k, v)| zvol.property(k, v))
                 .create(&self.dataset, size)?;
             format!("Creating volume {} with size {}", self.dataset, size)
         } else {
-            self.properties
+            let filesystem = self
+                .properties
                 .iter()
                 .fold(zfs::Zfs::filesystem(), |zfs, (k, v)| zfs.property(k, v))
                 .create(&self.dataset)?;
-            format!("Creating filesystem {}", self.dataset)
+            if filesystem.canmount() == property::CanMount::On && !filesystem.is_mounted() {
+                format!(
+                    "Filesystem {} successfully created, but not mounted",
+                    self.dataset
+                )
+            } else {
+                format!("Creating filesystem {}", self.dataset)
+            }
         };
         Ok(text)
     }
@@ -39,6 +39,17 @@ pub unsafe fn nvlist_dup(nvl: *mut nvlist_t) -> Result<*mut nvlist_t, NvListErro
     }
 }
 
+#[inline]
+pub unsafe fn nvlist_unpack(buf: *mut c_char, size: size_t) -> Result<*mut nvlist_t, NvListError> {
+    let mut nvl = mem::MaybeUninit::uninit();
+    match sys::nvlist_unpack(buf, size, nvl.as_mut_ptr(), RESERVED_FLAG_0) {
+        0 => Ok(nvl.assume_init()),
+        libc::ENOMEM => Err(NvListError::OutOfMemory),
+        // EINVAL, EFAULT or ENOTSUP - malformed buffer or unsupported encoding
+        _ => Err(NvListError::InvalidArgument),
+    }
+}
+
 macro_rules! nvlist_lookup {
     ($lookup:ident, $output:ty) => {
         #[inline]
@@ -3,7 +3,8 @@ use std::path::PathBuf;
 
 fn main() {
     let lzc = pkg_config::Config::new()
-        .atleast_version("0.8")
+        // OpenZFS 2.2 API: error scrub, blake3, libshare protocols, libzutil import handle
+        .atleast_version("2.2")
         .cargo_metadata(false)
         // Yes, this is on purpose 'libzfs_core' rather than 'libzfs'
         .probe("libzfs_core")
@@ -38,12 +39,35 @@ fn main() {
         .allowlist_type("zpool_handle_t")
         .allowlist_type("libzfs_handle_t")
         .allowlist_type("zfs_canmount_type_t")
+        .allowlist_type("zprop_source_t")
+        .constified_enum_module("zprop_source_t")
+        .allowlist_type("zprop_type_t")
+        .allowlist_type("importargs_t")
+        .allowlist_type("libpc_handle_t")
+        .allowlist_type("lpc_error_t")
+        .constified_enum_module("lpc_error")
+        .allowlist_type("pool_state_t")
+        .allowlist_type("vdev_state_t")
+        .allowlist_type("vdev_aux_t")
+        .allowlist_type("vdev_stat_t")
+        .allowlist_type("pool_scan_stat_t")
+        .allowlist_type("pool_scan_func_t")
+        .constified_enum_module("pool_scan_func_t")
+        .allowlist_type("pool_scrub_cmd_t")
+        .allowlist_type("dsl_scan_state_t")
         .allowlist_function(r"libzfs_\w*")
+        .allowlist_function("libpc_error_description")
         .allowlist_function(r"zpool_\w*")
         .allowlist_function(r"zfs_\w*")
         .allowlist_function(r"zvol_\w*")
         .allowlist_var(r"ZPOOL_CONFIG_\w*")
+        .allowlist_var(r"ZPOOL_HIST_\w*")
         .allowlist_var(r"ZPOOL_LOAD_\w*")
+        .allowlist_var(r"ZPOOL_\w*_REWIND")
+        .allowlist_var(r"ZFS_IMPORT_\w*")
+        .allowlist_var(r"ZFS_ONLINE_\w*")
+        .allowlist_var("ZEVENT_NONBLOCK")
+        .allowlist_var("libzfs_config_ops")
         .allowlist_var("ZFS_MAXPROPLEN")
         .allowlist_var("ZPOOL_MAXPROPLEN")
         .blocklist_item("boolean_t")
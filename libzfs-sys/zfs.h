@@ -1 +1,2 @@
 #include <libzfs.h>
+#include <libzutil.h>
@@ -22,6 +22,18 @@ use once_cell::sync::Lazy;
 use razor_libnvpair as libnvpair;
 use razor_libzfs_sys as sys;
 
+pub use sys::dsl_scan_state_t;
+pub use sys::importargs_t;
+pub use sys::libpc_handle_t;
+pub use sys::lpc_error;
+pub use sys::lpc_error_t;
+pub use sys::pool_scan_func_t;
+pub use sys::pool_scan_stat_t;
+pub use sys::pool_scrub_cmd_t;
+pub use sys::pool_state_t;
+pub use sys::vdev_aux_t;
+pub use sys::vdev_stat_t;
+pub use sys::vdev_state_t;
 pub use sys::zfs_canmount_type_t;
 pub use sys::zfs_error;
 pub use sys::zfs_error_t;
@@ -31,12 +43,26 @@ pub use sys::zfs_type_t;
 pub use sys::zfs_userquota_prop_t;
 pub use sys::zpool_handle_t;
 pub use sys::zpool_prop_t;
+pub use sys::zprop_source_t;
+pub use sys::zprop_type_t;
+pub use sys::ZEVENT_NONBLOCK;
+pub use sys::ZFS_IMPORT_ANY_HOST;
+pub use sys::ZFS_IMPORT_NORMAL;
+pub use sys::ZFS_MAXPROPLEN;
+pub use sys::ZFS_ONLINE_EXPAND;
+pub use sys::ZPOOL_DO_REWIND;
+pub use sys::ZPOOL_EXTREME_REWIND;
+pub use sys::ZPOOL_MAXPROPLEN;
+pub use sys::ZPOOL_NO_REWIND;
 
+pub use names::zpool_config;
+pub use names::zpool_hist;
 pub use version::Version;
 
 use handle::LIBZFS_HANDLE;
 
 mod handle;
+mod names;
 mod version;
 
 pub unsafe fn libzfs_errno() -> libc::c_int {
@@ -81,6 +107,11 @@ pub unsafe fn zfs_get_all_props(handle: *mut sys::zfs_handle_t) -> *mut libnvpai
     sys::zfs_get_all_props(handle)
 }
 
+pub unsafe fn zfs_get_user_props(handle: *mut sys::zfs_handle_t) -> *mut libnvpair::nvlist_t {
+    Lazy::force(&LIBZFS_HANDLE);
+    sys::zfs_get_user_props(handle)
+}
+
 pub unsafe fn zfs_prop_get_numeric(
     handle: *mut sys::zfs_handle_t,
     property: sys::zfs_prop_t,
@@ -105,6 +136,31 @@ pub unsafe fn zfs_prop_get_numeric(
     }
 }
 
+#[allow(clippy::too_many_arguments)]
+pub unsafe fn zfs_prop_get(
+    handle: *mut sys::zfs_handle_t,
+    property: sys::zfs_prop_t,
+    buf: *mut libc::c_char,
+    len: usize,
+    src: *mut sys::zprop_source_t::Type,
+    statbuf: *mut libc::c_char,
+    statlen: usize,
+    literal: bool,
+) -> libc::c_int {
+    Lazy::force(&LIBZFS_HANDLE);
+    let literal = literal.into();
+    sys::zfs_prop_get(handle, property, buf, len, src, statbuf, statlen, literal)
+}
+
+pub unsafe fn zfs_prop_inherit(
+    handle: *mut sys::zfs_handle_t,
+    name: *const libc::c_char,
+    received: bool,
+) -> libc::c_int {
+    Lazy::force(&LIBZFS_HANDLE);
+    sys::zfs_prop_inherit(handle, name, received.into())
+}
+
 pub unsafe fn zfs_prop_get_int(handle: *mut sys::zfs_handle_t, property: sys::zfs_prop_t) -> u64 {
     Lazy::force(&LIBZFS_HANDLE);
     sys::zfs_prop_get_int(handle, property)
@@ -133,6 +189,56 @@ pub unsafe fn zfs_prop_default_numeric(property: sys::zfs_prop_t) -> u64 {
     sys::zfs_prop_default_numeric(property)
 }
 
+pub unsafe fn zfs_prop_readonly(property: sys::zfs_prop_t) -> bool {
+    Lazy::force(&LIBZFS_HANDLE);
+    sys::zfs_prop_readonly(property) == libnvpair::boolean_t::B_TRUE
+}
+
+pub unsafe fn zfs_prop_setonce(property: sys::zfs_prop_t) -> bool {
+    Lazy::force(&LIBZFS_HANDLE);
+    sys::zfs_prop_setonce(property) == libnvpair::boolean_t::B_TRUE
+}
+
+pub unsafe fn zfs_prop_inheritable(property: sys::zfs_prop_t) -> bool {
+    Lazy::force(&LIBZFS_HANDLE);
+    sys::zfs_prop_inheritable(property) == libnvpair::boolean_t::B_TRUE
+}
+
+pub unsafe fn zfs_prop_visible(property: sys::zfs_prop_t) -> bool {
+    Lazy::force(&LIBZFS_HANDLE);
+    sys::zfs_prop_visible(property) == libnvpair::boolean_t::B_TRUE
+}
+
+pub unsafe fn zfs_prop_valid_for_type(property: sys::zfs_prop_t, r#type: zfs_type_t) -> bool {
+    Lazy::force(&LIBZFS_HANDLE);
+    let headcheck = false.into();
+    sys::zfs_prop_valid_for_type(property as libc::c_int, r#type, headcheck)
+        == libnvpair::boolean_t::B_TRUE
+}
+
+pub unsafe fn zfs_prop_get_type(property: sys::zfs_prop_t) -> sys::zprop_type_t {
+    Lazy::force(&LIBZFS_HANDLE);
+    sys::zfs_prop_get_type(property)
+}
+
+pub unsafe fn zfs_prop_index_to_string(
+    property: sys::zfs_prop_t,
+    index: u64,
+    string: *mut *const libc::c_char,
+) -> libc::c_int {
+    Lazy::force(&LIBZFS_HANDLE);
+    sys::zfs_prop_index_to_string(property, index, string)
+}
+
+pub unsafe fn zfs_prop_string_to_index(
+    property: sys::zfs_prop_t,
+    string: *const libc::c_char,
+    index: *mut u64,
+) -> libc::c_int {
+    Lazy::force(&LIBZFS_HANDLE);
+    sys::zfs_prop_string_to_index(property, string, index)
+}
+
 pub unsafe fn zfs_refresh_properties(dataset_handle: *mut sys::zfs_handle_t) {
     Lazy::force(&LIBZFS_HANDLE);
     sys::zfs_refresh_properties(dataset_handle)
@@ -141,13 +247,12 @@ pub unsafe fn zfs_refresh_properties(dataset_handle: *mut sys::zfs_handle_t) {
 pub unsafe fn zfs_valid_proplist(
     r#type: zfs_type_t,
     nvl: *mut libnvpair::nvlist_t,
-    zoned: bool,
+    zoned: u64,
     dataset_handle: *mut sys::zfs_handle_t,
     zpool_handle: *mut zpool_handle_t,
     key_params_ok: bool,
     err_buf: *const libc::c_char,
 ) -> *mut libnvpair::nvlist_t {
-    let zoned = zoned.into();
     let key_params_ok = key_params_ok.into();
     sys::zfs_valid_proplist(
         LIBZFS_HANDLE.handle(),
@@ -209,6 +314,292 @@ pub unsafe fn zfs_create(
     sys::zfs_create(LIBZFS_HANDLE.handle(), path, r#type, props)
 }
 
+/// Mount flags, as defined by libspl `sys/mount.h`
+pub const MS_FORCE: libc::c_int = 0x1;
+pub const MS_OVERLAY: libc::c_int = 0x4;
+
+pub unsafe fn zfs_mount(
+    handle: *mut sys::zfs_handle_t,
+    options: *const libc::c_char,
+    flags: libc::c_int,
+) -> libc::c_int {
+    Lazy::force(&LIBZFS_HANDLE);
+    sys::zfs_mount(handle, options, flags)
+}
+
+pub unsafe fn zfs_unmount(
+    handle: *mut sys::zfs_handle_t,
+    mountpoint: *const libc::c_char,
+    flags: libc::c_int,
+) -> libc::c_int {
+    Lazy::force(&LIBZFS_HANDLE);
+    sys::zfs_unmount(handle, mountpoint, flags)
+}
+
+pub unsafe fn zfs_unmountall(handle: *mut sys::zfs_handle_t, flags: libc::c_int) -> libc::c_int {
+    Lazy::force(&LIBZFS_HANDLE);
+    sys::zfs_unmountall(handle, flags)
+}
+
+/// Returns true if mounted, `mountpoint` is then set to the string to be freed by the caller
+pub unsafe fn zfs_is_mounted(
+    handle: *mut sys::zfs_handle_t,
+    mountpoint: *mut *mut libc::c_char,
+) -> bool {
+    Lazy::force(&LIBZFS_HANDLE);
+    sys::zfs_is_mounted(handle, mountpoint) == libnvpair::boolean_t::B_TRUE
+}
+
+pub unsafe fn zfs_share(
+    handle: *mut sys::zfs_handle_t,
+    proto: *const sys::sa_protocol,
+) -> libc::c_int {
+    Lazy::force(&LIBZFS_HANDLE);
+    sys::zfs_share(handle, proto)
+}
+
+pub unsafe fn zfs_commit_shares(proto: *const sys::sa_protocol) {
+    Lazy::force(&LIBZFS_HANDLE);
+    sys::zfs_commit_shares(proto)
+}
+
+pub unsafe fn zpool_open(name: *const libc::c_char) -> *mut zpool_handle_t {
+    sys::zpool_open(LIBZFS_HANDLE.handle(), name)
+}
+
+pub unsafe fn zpool_close(handle: *mut zpool_handle_t) {
+    Lazy::force(&LIBZFS_HANDLE);
+    sys::zpool_close(handle);
+}
+
+pub unsafe fn zpool_get_name(handle: *mut zpool_handle_t) -> *const libc::c_char {
+    Lazy::force(&LIBZFS_HANDLE);
+    sys::zpool_get_name(handle)
+}
+
+pub unsafe fn zpool_get_prop(
+    handle: *mut zpool_handle_t,
+    property: zpool_prop_t,
+    buf: *mut libc::c_char,
+    len: usize,
+    literal: bool,
+) -> libc::c_int {
+    Lazy::force(&LIBZFS_HANDLE);
+    let mut src = mem::MaybeUninit::uninit();
+    let literal = literal.into();
+    sys::zpool_get_prop(handle, property, buf, len, src.as_mut_ptr(), literal)
+}
+
+pub unsafe fn zpool_get_prop_int(handle: *mut zpool_handle_t, property: zpool_prop_t) -> u64 {
+    Lazy::force(&LIBZFS_HANDLE);
+    let mut src = mem::MaybeUninit::uninit();
+    sys::zpool_get_prop_int(handle, property, src.as_mut_ptr())
+}
+
+pub unsafe fn zpool_prop_to_name(property: zpool_prop_t) -> *const libc::c_char {
+    Lazy::force(&LIBZFS_HANDLE);
+    sys::zpool_prop_to_name(property)
+}
+
+pub unsafe fn zpool_create(
+    name: *const libc::c_char,
+    nvroot: *mut libnvpair::nvlist_t,
+    props: *mut libnvpair::nvlist_t,
+    fsprops: *mut libnvpair::nvlist_t,
+) -> libc::c_int {
+    sys::zpool_create(LIBZFS_HANDLE.handle(), name, nvroot, props, fsprops)
+}
+
+pub unsafe fn zpool_destroy(handle: *mut zpool_handle_t, log: *const libc::c_char) -> libc::c_int {
+    Lazy::force(&LIBZFS_HANDLE);
+    sys::zpool_destroy(handle, log)
+}
+
+pub unsafe fn zpool_scan(
+    handle: *mut zpool_handle_t,
+    func: pool_scan_func_t::Type,
+    cmd: pool_scrub_cmd_t,
+) -> libc::c_int {
+    Lazy::force(&LIBZFS_HANDLE);
+    sys::zpool_scan(handle, func, cmd)
+}
+
+/// `zpool_events_seek()` positions, not usable from bindgen (defined via UINT64_MAX)
+pub const ZEVENT_SEEK_START: u64 = 0;
+pub const ZEVENT_SEEK_END: u64 = u64::MAX;
+
+pub unsafe fn zpool_events_next(
+    event: *mut *mut libnvpair::nvlist_t,
+    dropped: *mut libc::c_int,
+    flags: libc::c_uint,
+    zevent_fd: libc::c_int,
+) -> libc::c_int {
+    sys::zpool_events_next(LIBZFS_HANDLE.handle(), event, dropped, flags, zevent_fd)
+}
+
+pub unsafe fn zpool_events_clear(count: *mut libc::c_int) -> libc::c_int {
+    sys::zpool_events_clear(LIBZFS_HANDLE.handle(), count)
+}
+
+pub unsafe fn zpool_events_seek(eid: u64, zevent_fd: libc::c_int) -> libc::c_int {
+    sys::zpool_events_seek(LIBZFS_HANDLE.handle(), eid, zevent_fd)
+}
+
+pub unsafe fn zpool_add(
+    handle: *mut zpool_handle_t,
+    nvroot: *mut libnvpair::nvlist_t,
+) -> libc::c_int {
+    Lazy::force(&LIBZFS_HANDLE);
+    sys::zpool_add(handle, nvroot)
+}
+
+pub unsafe fn zpool_vdev_attach(
+    handle: *mut zpool_handle_t,
+    old: *const libc::c_char,
+    new: *const libc::c_char,
+    nvroot: *mut libnvpair::nvlist_t,
+    replacing: bool,
+    rebuild: bool,
+) -> libc::c_int {
+    Lazy::force(&LIBZFS_HANDLE);
+    let rebuild = rebuild.into();
+    sys::zpool_vdev_attach(handle, old, new, nvroot, replacing as libc::c_int, rebuild)
+}
+
+pub unsafe fn zpool_vdev_detach(
+    handle: *mut zpool_handle_t,
+    path: *const libc::c_char,
+) -> libc::c_int {
+    Lazy::force(&LIBZFS_HANDLE);
+    sys::zpool_vdev_detach(handle, path)
+}
+
+pub unsafe fn zpool_vdev_online(
+    handle: *mut zpool_handle_t,
+    path: *const libc::c_char,
+    flags: libc::c_int,
+    newstate: *mut vdev_state_t,
+) -> libc::c_int {
+    Lazy::force(&LIBZFS_HANDLE);
+    sys::zpool_vdev_online(handle, path, flags, newstate)
+}
+
+pub unsafe fn zpool_vdev_offline(
+    handle: *mut zpool_handle_t,
+    path: *const libc::c_char,
+    temporary: bool,
+) -> libc::c_int {
+    Lazy::force(&LIBZFS_HANDLE);
+    sys::zpool_vdev_offline(handle, path, temporary.into())
+}
+
+pub unsafe fn zpool_clear(
+    handle: *mut zpool_handle_t,
+    path: *const libc::c_char,
+    rewind: *mut libnvpair::nvlist_t,
+) -> libc::c_int {
+    Lazy::force(&LIBZFS_HANDLE);
+    sys::zpool_clear(handle, path, rewind)
+}
+
+pub unsafe fn zpool_vdev_remove(
+    handle: *mut zpool_handle_t,
+    path: *const libc::c_char,
+) -> libc::c_int {
+    Lazy::force(&LIBZFS_HANDLE);
+    sys::zpool_vdev_remove(handle, path)
+}
+
+pub unsafe fn zpool_vdev_remove_cancel(handle: *mut zpool_handle_t) -> libc::c_int {
+    Lazy::force(&LIBZFS_HANDLE);
+    sys::zpool_vdev_remove_cancel(handle)
+}
+
+pub unsafe fn zpool_get_history(
+    handle: *mut zpool_handle_t,
+    history: *mut *mut libnvpair::nvlist_t,
+    offset: *mut u64,
+    eof: *mut bool,
+) -> libc::c_int {
+    Lazy::force(&LIBZFS_HANDLE);
+    let mut boolean = libnvpair::boolean_t::B_FALSE;
+    let rc = sys::zpool_get_history(handle, history, offset, &mut boolean);
+    *eof = boolean == libnvpair::boolean_t::B_TRUE;
+    rc
+}
+
+pub unsafe fn zpool_get_config(handle: *mut zpool_handle_t) -> *mut libnvpair::nvlist_t {
+    Lazy::force(&LIBZFS_HANDLE);
+    sys::zpool_get_config(handle, ptr::null_mut())
+}
+
+pub unsafe fn zpool_refresh_stats(handle: *mut zpool_handle_t, missing: *mut bool) -> libc::c_int {
+    Lazy::force(&LIBZFS_HANDLE);
+    let mut boolean = libnvpair::boolean_t::B_FALSE;
+    let rc = sys::zpool_refresh_stats(handle, &mut boolean);
+    *missing = boolean == libnvpair::boolean_t::B_TRUE;
+    rc
+}
+
+/// Decode stats struct (e.g. `vdev_stat_t` or `pool_scan_stat_t`) from the uint64 array
+/// found in the pool config. Fields missing from the array (older kernel module)
+/// are left zeroed, extra array elements (newer kernel module) are ignored.
+///
+/// # Safety
+///
+/// `T` must consist of `u64` fields only.
+///
+pub unsafe fn stat_from_array<T>(array: &[u64]) -> T {
+    let mut stat = mem::MaybeUninit::<T>::zeroed();
+    let len = array.len().min(mem::size_of::<T>() / mem::size_of::<u64>());
+    ptr::copy_nonoverlapping(array.as_ptr(), stat.as_mut_ptr().cast::<u64>(), len);
+    stat.assume_init()
+}
+
+pub unsafe fn zpool_export(
+    handle: *mut zpool_handle_t,
+    force: bool,
+    log: *const libc::c_char,
+) -> libc::c_int {
+    Lazy::force(&LIBZFS_HANDLE);
+    let force = force.into();
+    sys::zpool_export(handle, force, log)
+}
+
+/// libzutil handle for `zpool_search_import()`, as `zpool import` sets it up, except
+/// that the errors are only recorded in the handle, not printed
+pub fn libpc_handle() -> libpc_handle_t {
+    // SAFETY: all zeroes is a valid libpc_handle_t, with lpc_printerr B_FALSE
+    let mut handle: libpc_handle_t = unsafe { mem::zeroed() };
+    handle.lpc_lib_handle = LIBZFS_HANDLE.handle().cast();
+    handle.lpc_ops = unsafe { ptr::addr_of!(sys::libzfs_config_ops) } as _;
+    handle
+}
+
+pub unsafe fn libpc_error_description(handle: *mut libpc_handle_t) -> *const libc::c_char {
+    sys::libpc_error_description(handle)
+}
+
+pub unsafe fn zpool_search_import(
+    handle: *mut libpc_handle_t,
+    args: *mut importargs_t,
+) -> *mut libnvpair::nvlist_t {
+    sys::zpool_search_import(handle, args)
+}
+
+pub unsafe fn zpool_import_props(
+    config: *mut libnvpair::nvlist_t,
+    newname: *const libc::c_char,
+    props: *mut libnvpair::nvlist_t,
+    flags: libc::c_int,
+) -> libc::c_int {
+    sys::zpool_import_props(LIBZFS_HANDLE.handle(), config, newname, props, flags)
+}
+
+pub unsafe fn zpool_iter(callback: sys::zpool_iter_f, ptr: *mut libc::c_void) -> libc::c_int {
+    sys::zpool_iter(LIBZFS_HANDLE.handle(), callback, ptr)
+}
+
 pub fn zfs_version() -> Version {
     LIBZFS_HANDLE.version().clone()
 }
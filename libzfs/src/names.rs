@@ -0,0 +1,61 @@
+//! `ZPOOL_CONFIG_*` and `ZPOOL_HIST_*` nvlist names. They are bound as NUL terminated
+//! byte strings, while the nvlist API takes `&str`.
+
+use super::sys;
+
+macro_rules! names {
+    ($($name:ident),* $(,)?) => {
+        $(pub const $name: &str = super::name(super::sys::$name);)*
+    };
+}
+
+const fn name(bytes: &'static [u8]) -> &'static str {
+    let bytes = match bytes {
+        [name @ .., 0] => name,
+        name => name,
+    };
+    // SAFETY: the names are ASCII C string literals
+    unsafe { std::str::from_utf8_unchecked(bytes) }
+}
+
+pub mod zpool_config {
+    names!(
+        ZPOOL_CONFIG_ALLOCATION_BIAS,
+        ZPOOL_CONFIG_CHILDREN,
+        ZPOOL_CONFIG_DRAID_NDATA,
+        ZPOOL_CONFIG_DRAID_NGROUPS,
+        ZPOOL_CONFIG_DRAID_NSPARES,
+        ZPOOL_CONFIG_GUID,
+        ZPOOL_CONFIG_IS_LOG,
+        ZPOOL_CONFIG_L2CACHE,
+        ZPOOL_CONFIG_NPARITY,
+        ZPOOL_CONFIG_PATH,
+        ZPOOL_CONFIG_POOL_GUID,
+        ZPOOL_CONFIG_POOL_NAME,
+        ZPOOL_CONFIG_POOL_STATE,
+        ZPOOL_CONFIG_SCAN_STATS,
+        ZPOOL_CONFIG_SPARES,
+        ZPOOL_CONFIG_TYPE,
+        ZPOOL_CONFIG_VDEV_STATS,
+        ZPOOL_CONFIG_VDEV_TREE,
+        ZPOOL_CONFIG_WHOLE_DISK,
+    );
+}
+
+pub mod zpool_hist {
+    names!(
+        ZPOOL_HIST_CMD,
+        ZPOOL_HIST_DSNAME,
+        ZPOOL_HIST_ERRNO,
+        ZPOOL_HIST_HOST,
+        ZPOOL_HIST_INT_EVENT,
+        ZPOOL_HIST_INT_NAME,
+        ZPOOL_HIST_INT_STR,
+        ZPOOL_HIST_IOCTL,
+        ZPOOL_HIST_RECORD,
+        ZPOOL_HIST_TIME,
+        ZPOOL_HIST_TXG,
+        ZPOOL_HIST_WHO,
+        ZPOOL_HIST_ZONE,
+    );
+}
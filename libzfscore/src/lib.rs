@@ -30,6 +30,8 @@ pub use sys::pool_trim_func_t;
 
 #[cfg(feature = "wait")]
 pub use sys::zfs_wait_activity_t;
+#[cfg(feature = "wait")]
+pub use sys::zpool_wait_activity_t;
 
 mod lzc;
 
@@ -475,6 +477,51 @@ pub unsafe fn lzc_pool_checkpoint_discard(pool: *const libc::c_char) -> libc::c_
     sys::lzc_pool_checkpoint_discard(pool)
 }
 
+pub unsafe fn lzc_channel_program(
+    pool: *const libc::c_char,
+    program: *const libc::c_char,
+    instrlimit: u64,
+    memlimit: u64,
+    argnvl: *mut libnvpair::nvlist_t,
+    outnvl: *mut *mut libnvpair::nvlist_t,
+) -> libc::c_int {
+    Lazy::force(&lzc::LIBZFS_CORE);
+    sys::lzc_channel_program(pool, program, instrlimit, memlimit, argnvl, outnvl)
+}
+
+pub unsafe fn lzc_channel_program_nosync(
+    pool: *const libc::c_char,
+    program: *const libc::c_char,
+    instrlimit: u64,
+    memlimit: u64,
+    argnvl: *mut libnvpair::nvlist_t,
+    outnvl: *mut *mut libnvpair::nvlist_t,
+) -> libc::c_int {
+    Lazy::force(&lzc::LIBZFS_CORE);
+    sys::lzc_channel_program_nosync(pool, program, instrlimit, memlimit, argnvl, outnvl)
+}
+
+#[cfg(feature = "wait")]
+pub unsafe fn lzc_wait(
+    pool: *const libc::c_char,
+    activity: zpool_wait_activity_t,
+    waited: *mut libnvpair::boolean_t,
+) -> libc::c_int {
+    Lazy::force(&lzc::LIBZFS_CORE);
+    sys::lzc_wait(pool, activity, waited)
+}
+
+#[cfg(feature = "wait")]
+pub unsafe fn lzc_wait_tag(
+    pool: *const libc::c_char,
+    activity: zpool_wait_activity_t,
+    tag: u64,
+    waited: *mut libnvpair::boolean_t,
+) -> libc::c_int {
+    Lazy::force(&lzc::LIBZFS_CORE);
+    sys::lzc_wait_tag(pool, activity, tag, waited)
+}
+
 #[cfg(feature = "wait")]
 pub unsafe fn lzc_wait_fs(
     name: *const libc::c_char,
@@ -33,6 +33,12 @@ impl<'a, T> NvListRef<'a, T> {
         }
     }
 
+    /// Deep copy of the referenced nvlist, owned by the caller
+    pub fn dup(&self) -> Result<NvList, NvListError> {
+        let nvl = unsafe { libnvpair::nvlist_dup(self.nvl)? };
+        Ok(NvList { nvl })
+    }
+
     fn borrow(&self) -> NvListRef<'_, Self> {
         NvListRef::from_raw(self.nvl, self)
     }
@@ -45,6 +51,12 @@ impl NvList {
         Self { nvl }
     }
 
+    /// Unpack nvlist from the packed (native or XDR encoded) buffer
+    pub fn unpack(buf: &[u8]) -> Result<Self, NvListError> {
+        let nvl = unsafe { libnvpair::nvlist_unpack(buf.as_ptr() as *mut _, buf.len())? };
+        Ok(Self { nvl })
+    }
+
     fn borrow(&self) -> NvListRef<'_, Self> {
         NvListRef::from_raw(self.nvl, self)
     }
@@ -218,6 +218,14 @@ impl NvList {
         unsafe { libnvpair::nvlist_add_nvlist(self.nvl, name.as_ptr(), *nvl) }
     }
 
+    /// Add named nvlist array/slice to this nvlist
+    pub fn add_nvlist_array<N>(&mut self, name: impl AsRef<str>, v: &[N]) -> Result<(), NvListError>
+    where
+        N: AsRef<*mut libnvpair::nvlist_t>,
+    {
+        add_nvlist_array_impl(self.nvl, name, v)
+    }
+
     /// Add named boolean array/slice to this nvlist
     pub fn add_boolean_array(
         &mut self,
@@ -283,6 +291,14 @@ impl<'a, T> NvListRef<'a, T> {
         unsafe { libnvpair::nvlist_add_nvlist(self.nvl, name.as_ptr(), *nvl) }
     }
 
+    /// Add named nvlist array/slice to this nvlist
+    pub fn add_nvlist_array<N>(&mut self, name: impl AsRef<str>, v: &[N]) -> Result<(), NvListError>
+    where
+        N: AsRef<*mut libnvpair::nvlist_t>,
+    {
+        add_nvlist_array_impl(self.nvl, name, v)
+    }
+
     /// Add named boolean array/slice to this nvlist
     pub fn add_boolean_array(
         &mut self,
@@ -350,6 +366,22 @@ fn add_boolean_array_impl(
     unsafe { libnvpair::nvlist_add_boolean_array(nvl, name.as_ptr(), v.as_mut_ptr(), nelem) }
 }
 
+#[inline]
+fn add_nvlist_array_impl<T>(
+    nvl: *mut libnvpair::nvlist_t,
+    name: impl AsRef<str>,
+    v: &[T],
+) -> Result<(), NvListError>
+where
+    T: AsRef<*mut libnvpair::nvlist_t>,
+{
+    let name = cstring(name)?;
+    // nvlists are copied into the array, the originals are still owned by the caller
+    let mut v = v.iter().map(|item| *item.as_ref()).collect::<Vec<_>>();
+    let nelem = v.len() as u32;
+    unsafe { libnvpair::nvlist_add_nvlist_array(nvl, name.as_ptr(), v.as_mut_ptr(), nelem) }
+}
+
 #[inline]
 fn add_string_array_impl<T>(
     nvl: *mut libnvpair::nvlist_t,
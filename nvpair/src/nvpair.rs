@@ -379,6 +379,28 @@ impl NvPair {
                 .collect::<Vec<_>>()
         }
     }
+
+    /// Returns the `Vec<NvListRef<'_, Self>>` value of the nvpair.
+    /// The returning `NvListRef` objects track the parent `NvPair` object lifetime
+    /// and do not outlive it.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the type of this nvpair is not nvlist array.
+    ///
+    #[inline]
+    pub fn nvlist_array(&self) -> Vec<NvListRef<'_, Self>> {
+        unsafe {
+            let (data, len) = libnvpair::nvpair_value_nvlist_array(self.nvp)
+                .expect("NvPair type is not nvlist array");
+            debug_assert!(!data.is_null());
+            let len = len as usize;
+            slice::from_raw_parts(data, len)
+                .iter()
+                .map(|nvl| NvListRef::from_raw(*nvl, self))
+                .collect::<Vec<_>>()
+        }
+    }
 }
 
 impl NvPair {
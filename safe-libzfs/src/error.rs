@@ -1,6 +1,7 @@
 use std::borrow::Cow;
 use std::fmt;
 
+use libzfs::lpc_error;
 use libzfs::zfs_error;
 
 use super::*;
@@ -24,6 +25,14 @@ impl ZfsError {
         self.error == zfs_error::EZFS_SUCCESS
     }
 
+    pub fn code(&self) -> libzfs::zfs_error_t {
+        self.error
+    }
+
+    pub fn description(&self) -> &str {
+        &self.description
+    }
+
     pub fn from_libzfs_errno() -> Self {
         let code = libzfs_errno();
         let error = code as u32;
@@ -32,6 +41,26 @@ impl ZfsError {
         Self { error, description }
     }
 
+    /// Error recorded in the libzutil handle by the pool search
+    pub fn from_libpc(handle: &mut libzfs::libpc_handle_t) -> Self {
+        let error = match handle.lpc_error as libzfs::lpc_error_t {
+            lpc_error::LPC_BADCACHE => zfs_error::EZFS_BADCACHE,
+            lpc_error::LPC_BADPATH => zfs_error::EZFS_BADPATH,
+            lpc_error::LPC_NOMEM => zfs_error::EZFS_NOMEM,
+            lpc_error::LPC_EACCESS => zfs_error::EZFS_EACESS,
+            _ => zfs_error::EZFS_UNKNOWN,
+        };
+        let description = unsafe {
+            let cstr = libzfs::libpc_error_description(handle);
+            ffi::CStr::from_ptr(cstr)
+                .to_string_lossy()
+                .into_owned()
+                .into()
+        };
+
+        Self { error, description }
+    }
+
     pub fn from_rc(code: i32) -> Self {
         if code == -1 {
             Self::from_libzfs_errno()
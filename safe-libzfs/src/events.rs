@@ -0,0 +1,59 @@
+use std::fs;
+use std::os::unix::io::AsRawFd;
+use std::ptr;
+
+use super::*;
+
+const ZFS_DEV: &str = "/dev/zfs";
+
+/// Private cursor into the kernel event queue (zevent). Each handle tracks its own
+/// position, so independent readers do not steal the events from each other.
+///
+#[derive(Debug)]
+pub struct ZeventHandle {
+    dev: fs::File,
+}
+
+impl ZeventHandle {
+    pub fn new() -> Result<Self, ZfsError> {
+        let dev = fs::OpenOptions::new()
+            .read(true)
+            .write(true)
+            .open(ZFS_DEV)
+            .map_err(|err| ZfsError::from_rc(err.raw_os_error().unwrap_or(libc::EIO)))?;
+        Ok(Self { dev })
+    }
+
+    /// Next event along with the number of events dropped (overwritten in the kernel queue)
+    /// since the previous one. Without `block` returns `None` if there are no more events.
+    pub fn next(&self, block: bool) -> Result<Option<(nvpair::NvList, u32)>, ZfsError> {
+        let flags = if block { 0 } else { libzfs::ZEVENT_NONBLOCK };
+        let mut event = ptr::null_mut();
+        let mut dropped = 0;
+        let rc = unsafe {
+            libzfs::zpool_events_next(&mut event, &mut dropped, flags, self.dev.as_raw_fd())
+        };
+        if rc != 0 {
+            return Err(ZfsError::from_rc(rc));
+        }
+
+        if event.is_null() {
+            Ok(None)
+        } else {
+            Ok(Some((nvpair::NvList::from(event), dropped as u32)))
+        }
+    }
+
+    /// Move the cursor to the event `eid`, `ZEVENT_SEEK_START` or `ZEVENT_SEEK_END`
+    pub fn seek(&self, eid: u64) -> Result<(), ZfsError> {
+        let rc = unsafe { libzfs::zpool_events_seek(eid, self.dev.as_raw_fd()) };
+        ZfsError::from_rc(rc).result(())
+    }
+}
+
+/// Clear all the events from the kernel queue, returns the number of cleared events
+pub fn zpool_events_clear() -> Result<u32, ZfsError> {
+    let mut count = 0;
+    let rc = unsafe { libzfs::zpool_events_clear(&mut count) };
+    ZfsError::from_rc(rc).result(count as u32)
+}
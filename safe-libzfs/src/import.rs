@@ -0,0 +1,73 @@
+use std::mem;
+use std::ptr;
+
+use super::*;
+
+/// Pool search arguments, see `zpool_search_import()`
+#[derive(Debug, Default)]
+pub struct ImportArgs {
+    /// Directories (or devices) to search, default device directories if empty
+    pub paths: Vec<String>,
+    pub poolname: Option<String>,
+    pub guid: Option<u64>,
+    /// Read pool configurations from the cachefile instead of scanning the devices
+    pub cachefile: Option<String>,
+    pub can_be_active: bool,
+    pub scan: bool,
+    /// Load policy (rewind, txg)
+    pub policy: Option<nvpair::NvList>,
+}
+
+/// Search for importable pools, returns nvlist of pool configurations keyed by pool name
+pub fn zpool_search_import(args: &ImportArgs) -> Result<nvpair::NvList, ZfsError> {
+    let paths = args
+        .paths
+        .iter()
+        .map(cstring)
+        .collect::<Result<Vec<_>, _>>()?;
+    // paths needs to live until the end of this function
+    let mut path = paths
+        .iter()
+        .map(|path| path.as_ptr() as *mut libc::c_char)
+        .collect::<Vec<_>>();
+    let poolname = args.poolname.as_ref().map(cstring).transpose()?;
+    let cachefile = args.cachefile.as_ref().map(cstring).transpose()?;
+
+    let mut importargs: libzfs::importargs_t = unsafe { mem::zeroed() };
+    if !path.is_empty() {
+        importargs.path = path.as_mut_ptr();
+        importargs.paths = path.len() as libc::c_int;
+    }
+    importargs.poolname = poolname.as_ref().map_or(ptr::null(), |name| name.as_ptr());
+    importargs.guid = args.guid.unwrap_or_default();
+    importargs.cachefile = cachefile.as_ref().map_or(ptr::null(), |file| file.as_ptr());
+    importargs.can_be_active = args.can_be_active.into();
+    importargs.scan = args.scan.into();
+    importargs.policy = args
+        .policy
+        .as_ref()
+        .map_or(ptr::null_mut(), |policy| **policy);
+
+    let mut handle = libzfs::libpc_handle();
+    let pools = unsafe { libzfs::zpool_search_import(&mut handle, &mut importargs) };
+    if pools.is_null() {
+        Err(ZfsError::from_libpc(&mut handle))
+    } else {
+        Ok(nvpair::NvList::from(pools))
+    }
+}
+
+/// Import the pool described by `config` (as returned by `zpool_search_import()`),
+/// optionally under the `newname`.
+pub fn zpool_import(
+    config: &nvpair::NvList,
+    newname: Option<&str>,
+    props: impl Into<nvpair::NvList>,
+    flags: i32,
+) -> Result<(), ZfsError> {
+    let newname = newname.map(cstring).transpose()?;
+    let newname = newname.as_ref().map_or(ptr::null(), |name| name.as_ptr());
+    let props = props.into();
+    let rc = unsafe { libzfs::zpool_import_props(**config, newname, *props, flags) };
+    ZfsError::from_rc(rc).result(())
+}
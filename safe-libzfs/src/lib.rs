@@ -17,19 +17,70 @@
 //!
 
 use std::borrow::Cow;
+use std::collections::BTreeMap;
 use std::ffi;
 
 use razor_libzfs as libzfs;
 use razor_nvpair as nvpair;
 
+pub use libzfs::dsl_scan_state_t;
+pub use libzfs::pool_scan_func_t;
+pub use libzfs::pool_scan_stat_t;
+pub use libzfs::pool_scrub_cmd_t;
+pub use libzfs::pool_state_t;
+pub use libzfs::vdev_aux_t;
+pub use libzfs::vdev_stat_t;
+pub use libzfs::vdev_state_t;
 pub use libzfs::zfs_canmount_type_t;
+pub use libzfs::zfs_error;
+pub use libzfs::zfs_error_t;
 pub use libzfs::zfs_prop_t;
+pub use libzfs::zfs_type_t;
+pub use libzfs::zpool_config;
+pub use libzfs::zpool_hist;
+pub use libzfs::zpool_prop_t;
+pub use libzfs::zprop_type_t;
+pub use libzfs::MS_FORCE;
+pub use libzfs::MS_OVERLAY;
+pub use libzfs::ZEVENT_SEEK_END;
+pub use libzfs::ZEVENT_SEEK_START;
+pub use libzfs::ZFS_IMPORT_ANY_HOST;
+pub use libzfs::ZFS_IMPORT_NORMAL;
+pub use libzfs::ZFS_ONLINE_EXPAND;
+pub use libzfs::ZPOOL_DO_REWIND;
+pub use libzfs::ZPOOL_EXTREME_REWIND;
+pub use libzfs::ZPOOL_NO_REWIND;
 
 pub use self::collector::DatasetCollectorBuilder;
 pub use self::error::ZfsError;
+pub use self::events::zpool_events_clear;
+pub use self::events::ZeventHandle;
+pub use self::import::zpool_import;
+pub use self::import::zpool_search_import;
+pub use self::import::ImportArgs;
+pub use self::property::zfs_prop_get_type;
+pub use self::property::zfs_prop_index_to_string;
+pub use self::property::zfs_prop_inheritable;
+pub use self::property::zfs_prop_readonly;
+pub use self::property::zfs_prop_setonce;
+pub use self::property::zfs_prop_string_to_index;
+pub use self::property::zfs_prop_valid_for_type;
+pub use self::property::zfs_props;
+pub use self::property::zfs_valid_proplist;
+pub use self::property::PropSource;
+pub use self::zpool::pool_scan_stat;
+pub use self::zpool::vdev_stat;
+pub use self::zpool::zpool_create;
+pub use self::zpool::zpool_list;
+pub use self::zpool::zpool_prop_to_name;
+pub use self::zpool::ZpoolHandle;
 
 mod collector;
 mod error;
+mod events;
+mod import;
+mod property;
+mod zpool;
 
 #[derive(Debug)]
 pub struct ZfsHandle {
@@ -98,6 +149,105 @@ impl ZfsHandle {
         }
     }
 
+    /// Property value, in parsable (literal) form, along with its source
+    pub fn property_with_source(
+        &self,
+        property: zfs_prop_t,
+    ) -> Result<(String, PropSource), ZfsError> {
+        let mut buf = vec![0 as libc::c_char; libzfs::ZFS_MAXPROPLEN as usize];
+        let mut statbuf = vec![0 as libc::c_char; libzfs::ZFS_MAXPROPLEN as usize];
+        let mut source = libzfs::zprop_source_t::ZPROP_SRC_NONE;
+        let rc = unsafe {
+            libzfs::zfs_prop_get(
+                self.handle,
+                property,
+                buf.as_mut_ptr(),
+                buf.len(),
+                &mut source,
+                statbuf.as_mut_ptr(),
+                statbuf.len(),
+                true,
+            )
+        };
+        if rc != 0 {
+            return Err(ZfsError::from_rc(rc));
+        }
+        let (value, inherited_from) = unsafe {
+            (
+                ffi::CStr::from_ptr(buf.as_ptr())
+                    .to_string_lossy()
+                    .into_owned(),
+                ffi::CStr::from_ptr(statbuf.as_ptr())
+                    .to_string_lossy()
+                    .into_owned(),
+            )
+        };
+        Ok((value, PropSource::new(source, inherited_from)))
+    }
+
+    /// Clear the local value of the property, as `zfs inherit` does. With `received`
+    /// reverts to the received value (`zfs inherit -S`) if there is one.
+    pub fn inherit(&mut self, name: &str, received: bool) -> Result<(), ZfsError> {
+        let name = cstring(name)?;
+        let rc = unsafe { libzfs::zfs_prop_inherit(self.handle, name.as_ptr(), received) };
+        ZfsError::from_rc(rc)
+            .result(())
+            .map(|_| unsafe { libzfs::zfs_refresh_properties(self.handle) })
+    }
+
+    /// User (`module:property`) properties, along with their sources
+    pub fn user_properties(&self) -> BTreeMap<String, (String, PropSource)> {
+        let nvl = unsafe { libzfs::zfs_get_user_props(self.handle) };
+        let nvl = nvpair::NvListRef::from_raw(nvl, self);
+        property::user_properties(&nvl, &self.name())
+    }
+
+    /// Mount the filesystem at its `mountpoint`, with comma separated `options`.
+    /// Does nothing unless the filesystem is mountable (see `canmount` and `mountpoint`).
+    pub fn mount(&self, options: Option<&str>, flags: i32) -> Result<(), ZfsError> {
+        let options = options.map(cstring).transpose()?;
+        let options = options
+            .as_ref()
+            .map_or(std::ptr::null(), |options| options.as_ptr());
+        let rc = unsafe { libzfs::zfs_mount(self.handle, options, flags) };
+        ZfsError::from_rc(rc).result(())
+    }
+
+    /// Unshare and unmount the filesystem
+    pub fn unmount(&self, flags: i32) -> Result<(), ZfsError> {
+        let rc = unsafe { libzfs::zfs_unmount(self.handle, std::ptr::null(), flags) };
+        ZfsError::from_rc(rc).result(())
+    }
+
+    /// Unmount the filesystem along with all its descendants
+    pub fn unmount_all(&self, flags: i32) -> Result<(), ZfsError> {
+        let rc = unsafe { libzfs::zfs_unmountall(self.handle, flags) };
+        ZfsError::from_rc(rc).result(())
+    }
+
+    /// Current mountpoint, `None` if not mounted
+    pub fn mounted(&self) -> Option<String> {
+        let mut mountpoint = std::ptr::null_mut();
+        unsafe {
+            if !libzfs::zfs_is_mounted(self.handle, &mut mountpoint) {
+                return None;
+            }
+            let path = ffi::CStr::from_ptr(mountpoint)
+                .to_string_lossy()
+                .into_owned();
+            libc::free(mountpoint.cast());
+            Some(path)
+        }
+    }
+
+    /// Share the filesystem according to its `sharenfs` and `sharesmb` properties
+    pub fn share(&self) -> Result<(), ZfsError> {
+        // NULL protocol list means all the protocols
+        let rc = unsafe { libzfs::zfs_share(self.handle, std::ptr::null()) };
+        unsafe { libzfs::zfs_commit_shares(std::ptr::null()) };
+        ZfsError::from_rc(rc).result(())
+    }
+
     pub fn set_properties(&mut self, nvl: impl Into<nvpair::NvList>) -> Result<(), ZfsError> {
         let nvl = nvl.into();
         let rc = unsafe { libzfs::zfs_prop_set_list(self.handle, *nvl) };
@@ -119,9 +269,13 @@ impl Drop for ZfsHandle {
     }
 }
 
+/// Default value of the string property, empty if there is none
 pub fn zfs_prop_default_string(property: zfs_prop_t) -> Cow<'static, str> {
     unsafe {
         let cstr = libzfs::zfs_prop_default_string(property);
+        if cstr.is_null() {
+            return Cow::Borrowed("");
+        }
         ffi::CStr::from_ptr(cstr).to_string_lossy()
     }
 }
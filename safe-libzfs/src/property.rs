@@ -0,0 +1,151 @@
+use std::mem;
+use std::ptr;
+
+use super::*;
+
+use libzfs::zprop_source_t;
+
+const ZPROP_VALUE: &str = "value";
+const ZPROP_SOURCE: &str = "source";
+const ZPROP_SOURCE_VAL_RECVD: &str = "$recvd";
+
+/// Where the property value comes from, as shown in the `SOURCE` column of `zfs get`
+///
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum PropSource {
+    /// Read-only or statistic property
+    None,
+    Default,
+    /// Temporary mount option, e.g. `zfs mount -o ro`
+    Temporary,
+    Local,
+    /// Inherited from the named ancestor
+    Inherited(String),
+    /// Received with `zfs receive`
+    Received,
+}
+
+impl PropSource {
+    pub(crate) fn new(source: zprop_source_t::Type, inherited_from: String) -> Self {
+        match source {
+            zprop_source_t::ZPROP_SRC_DEFAULT => Self::Default,
+            zprop_source_t::ZPROP_SRC_TEMPORARY => Self::Temporary,
+            zprop_source_t::ZPROP_SRC_LOCAL => Self::Local,
+            zprop_source_t::ZPROP_SRC_INHERITED => Self::Inherited(inherited_from),
+            zprop_source_t::ZPROP_SRC_RECEIVED => Self::Received,
+            _ => Self::None,
+        }
+    }
+
+    /// User properties name their source dataset rather than the source kind
+    fn from_user_source(source: &str, dataset: &str) -> Self {
+        if source == dataset {
+            Self::Local
+        } else if source == ZPROP_SOURCE_VAL_RECVD {
+            Self::Received
+        } else {
+            Self::Inherited(source.to_string())
+        }
+    }
+
+    pub fn is_local(&self) -> bool {
+        matches!(self, Self::Local)
+    }
+}
+
+/// Decode `zfs_get_user_props()` list, `{ name: { value, source } }`
+pub(crate) fn user_properties<T>(
+    nvl: &nvpair::NvListRef<'_, T>,
+    dataset: &str,
+) -> BTreeMap<String, (String, PropSource)> {
+    nvl.iter()
+        .filter_map(|nvp| {
+            let prop = nvp.nvlist();
+            let value = prop.lookup_nvpair(ZPROP_VALUE).ok()??.string().into_owned();
+            let source = match prop.lookup_nvpair(ZPROP_SOURCE).ok()? {
+                Some(source) => PropSource::from_user_source(&source.string(), dataset),
+                None => PropSource::Local,
+            };
+            Some((nvp.name().into_owned(), (value, source)))
+        })
+        .collect()
+}
+
+/// All the visible dataset properties known to libzfs
+pub fn zfs_props() -> Vec<zfs_prop_t> {
+    (0..zfs_prop_t::ZFS_NUM_PROPS as i32)
+        // SAFETY: the values below ZFS_NUM_PROPS are all valid zfs_prop_t variants
+        .map(|property| unsafe { mem::transmute::<i32, zfs_prop_t>(property) })
+        .filter(|property| unsafe { libzfs::zfs_prop_visible(*property) })
+        .collect()
+}
+
+/// Also true for the properties that can only be set at creation, see `zfs_prop_setonce()`
+pub fn zfs_prop_readonly(property: zfs_prop_t) -> bool {
+    unsafe { libzfs::zfs_prop_readonly(property) }
+}
+
+pub fn zfs_prop_setonce(property: zfs_prop_t) -> bool {
+    unsafe { libzfs::zfs_prop_setonce(property) }
+}
+
+pub fn zfs_prop_inheritable(property: zfs_prop_t) -> bool {
+    unsafe { libzfs::zfs_prop_inheritable(property) }
+}
+
+pub fn zfs_prop_valid_for_type(property: zfs_prop_t, r#type: libzfs::zfs_type_t) -> bool {
+    unsafe { libzfs::zfs_prop_valid_for_type(property, r#type) }
+}
+
+pub fn zfs_prop_get_type(property: zfs_prop_t) -> libzfs::zprop_type_t {
+    unsafe { libzfs::zfs_prop_get_type(property) }
+}
+
+/// Name of the index property value, `None` if the property is not an index
+pub fn zfs_prop_index_to_string(property: zfs_prop_t, index: u64) -> Option<Cow<'static, str>> {
+    let mut cstr = ptr::null();
+    unsafe {
+        let rc = libzfs::zfs_prop_index_to_string(property, index, &mut cstr);
+        (rc == 0 && !cstr.is_null()).then(|| ffi::CStr::from_ptr(cstr).to_string_lossy())
+    }
+}
+
+/// Numeric value of the index property value name
+pub fn zfs_prop_string_to_index(property: zfs_prop_t, value: &str) -> Option<u64> {
+    let value = cstring(value).ok()?;
+    let mut index = 0;
+    let rc = unsafe { libzfs::zfs_prop_string_to_index(property, value.as_ptr(), &mut index) };
+    (rc == 0).then_some(index)
+}
+
+/// Validate (and normalize) the properties the way `zfs create` and `zfs set` do,
+/// for the existing `dataset` or the new one of the given type if `None`.
+pub fn zfs_valid_proplist(
+    r#type: zfs_type_t,
+    props: &nvpair::NvList,
+    dataset: Option<&ZfsHandle>,
+) -> Result<nvpair::NvList, ZfsError> {
+    let (zhp, zoned) = dataset.map_or((ptr::null_mut(), 0), |dataset| {
+        (
+            dataset.handle,
+            dataset.numeric_property(zfs_prop_t::ZFS_PROP_ZONED),
+        )
+    });
+    let errbuf = cstring("invalid property")?;
+    let nvl = unsafe {
+        libzfs::zfs_valid_proplist(
+            r#type,
+            **props,
+            zoned,
+            zhp,
+            ptr::null_mut(),
+            true,
+            errbuf.as_ptr(),
+        )
+    };
+    if nvl.is_null() {
+        Err(ZfsError::from_libzfs_errno())
+    } else {
+        Ok(nvpair::NvList::from(nvl))
+    }
+}
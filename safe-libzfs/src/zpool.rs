@@ -0,0 +1,262 @@
+use std::ptr;
+
+use super::*;
+
+use libzfs::zpool_prop_t;
+
+#[derive(Debug)]
+pub struct ZpoolHandle {
+    handle: *mut libzfs::zpool_handle_t,
+}
+
+unsafe impl ::std::marker::Send for ZpoolHandle {}
+
+impl ZpoolHandle {
+    pub fn new(name: ffi::CString) -> Result<Self, ZfsError> {
+        let handle = unsafe { libzfs::zpool_open(name.as_ptr()) };
+
+        if !handle.is_null() {
+            Ok(Self { handle })
+        } else {
+            Err(ZfsError::from_libzfs_errno())
+        }
+    }
+
+    pub fn name(&self) -> Cow<'_, str> {
+        unsafe {
+            let cstr = libzfs::zpool_get_name(self.handle);
+            ffi::CStr::from_ptr(cstr).to_string_lossy()
+        }
+    }
+
+    pub fn numeric_property(&self, property: zpool_prop_t) -> u64 {
+        unsafe { libzfs::zpool_get_prop_int(self.handle, property) }
+    }
+
+    /// Pool configuration, including the vdev tree with its statistics,
+    /// as of the last refresh
+    pub fn config(&self) -> nvpair::NvListRef<'_, Self> {
+        let config = unsafe { libzfs::zpool_get_config(self.handle) };
+        nvpair::NvListRef::from_raw(config, self)
+    }
+
+    /// Refresh the pool configuration and statistics.
+    /// Returns `true` if the pool is missing (e.g. exported meanwhile).
+    pub fn refresh_stats(&mut self) -> Result<bool, ZfsError> {
+        let mut missing = false;
+        let rc = unsafe { libzfs::zpool_refresh_stats(self.handle, &mut missing) };
+        ZfsError::from_rc(rc).result(missing)
+    }
+
+    /// Start, pause or stop scrub, resilver or error scrub. `POOL_SCAN_NONE` stops
+    /// the current scan.
+    pub fn scan(
+        &self,
+        func: libzfs::pool_scan_func_t::Type,
+        cmd: libzfs::pool_scrub_cmd_t,
+    ) -> Result<(), ZfsError> {
+        let rc = unsafe { libzfs::zpool_scan(self.handle, func, cmd) };
+        ZfsError::from_rc(rc).result(())
+    }
+
+    /// Add the vdevs of `nvroot` to the pool
+    pub fn add(&self, nvroot: impl Into<nvpair::NvList>) -> Result<(), ZfsError> {
+        let nvroot = nvroot.into();
+        let rc = unsafe { libzfs::zpool_add(self.handle, *nvroot) };
+        ZfsError::from_rc(rc).result(())
+    }
+
+    /// Attach `new` device (the only leaf of `nvroot`) to the `device` vdev, creating
+    /// or extending the mirror. With `replacing` the `device` is replaced instead.
+    pub fn vdev_attach(
+        &self,
+        device: impl AsRef<str>,
+        new: impl AsRef<str>,
+        nvroot: impl Into<nvpair::NvList>,
+        replacing: bool,
+    ) -> Result<(), ZfsError> {
+        let device = cstring(device)?;
+        let new = cstring(new)?;
+        let nvroot = nvroot.into();
+        let rc = unsafe {
+            libzfs::zpool_vdev_attach(
+                self.handle,
+                device.as_ptr(),
+                new.as_ptr(),
+                *nvroot,
+                replacing,
+                false,
+            )
+        };
+        ZfsError::from_rc(rc).result(())
+    }
+
+    pub fn vdev_detach(&self, device: impl AsRef<str>) -> Result<(), ZfsError> {
+        let device = cstring(device)?;
+        let rc = unsafe { libzfs::zpool_vdev_detach(self.handle, device.as_ptr()) };
+        ZfsError::from_rc(rc).result(())
+    }
+
+    /// Bring the device online, returns its new state
+    pub fn vdev_online(
+        &self,
+        device: impl AsRef<str>,
+        flags: u32,
+    ) -> Result<libzfs::vdev_state_t, ZfsError> {
+        let device = cstring(device)?;
+        let mut state = libzfs::vdev_state_t::VDEV_STATE_UNKNOWN;
+        let rc = unsafe {
+            libzfs::zpool_vdev_online(self.handle, device.as_ptr(), flags as i32, &mut state)
+        };
+        ZfsError::from_rc(rc).result(state)
+    }
+
+    /// Take the device offline. `temporary` offline state does not persist across reboots.
+    pub fn vdev_offline(&self, device: impl AsRef<str>, temporary: bool) -> Result<(), ZfsError> {
+        let device = cstring(device)?;
+        let rc = unsafe { libzfs::zpool_vdev_offline(self.handle, device.as_ptr(), temporary) };
+        ZfsError::from_rc(rc).result(())
+    }
+
+    /// Clear the device errors, or the errors of all the devices if `device` is `None`
+    pub fn clear(
+        &self,
+        device: Option<&str>,
+        rewind: impl Into<nvpair::NvList>,
+    ) -> Result<(), ZfsError> {
+        let device = device.map(cstring).transpose()?;
+        let path = device
+            .as_ref()
+            .map_or(ptr::null(), |device| device.as_ptr());
+        let rewind = rewind.into();
+        let rc = unsafe { libzfs::zpool_clear(self.handle, path, *rewind) };
+        ZfsError::from_rc(rc).result(())
+    }
+
+    /// Remove the device, evacuating its data to the other top-level vdevs if needed
+    pub fn vdev_remove(&self, device: impl AsRef<str>) -> Result<(), ZfsError> {
+        let device = cstring(device)?;
+        let rc = unsafe { libzfs::zpool_vdev_remove(self.handle, device.as_ptr()) };
+        ZfsError::from_rc(rc).result(())
+    }
+
+    /// Cancel the device removal in progress
+    pub fn vdev_remove_cancel(&self) -> Result<(), ZfsError> {
+        let rc = unsafe { libzfs::zpool_vdev_remove_cancel(self.handle) };
+        ZfsError::from_rc(rc).result(())
+    }
+
+    /// Chunk of the pool history starting at `offset`, which is advanced past the chunk.
+    /// Returns the chunk (with the records in `ZPOOL_HIST_RECORD` array) and whether
+    /// this is the end of the history.
+    pub fn history(&self, offset: &mut u64) -> Result<(nvpair::NvList, bool), ZfsError> {
+        let mut history = ptr::null_mut();
+        let mut eof = false;
+        let rc = unsafe { libzfs::zpool_get_history(self.handle, &mut history, offset, &mut eof) };
+        if rc != 0 {
+            return Err(ZfsError::from_rc(rc));
+        }
+
+        let history = if history.is_null() {
+            nvpair::NvList::new()
+        } else {
+            nvpair::NvList::from(history)
+        };
+        Ok((history, eof))
+    }
+
+    /// Destroy the pool. `log` is recorded in the pool history of the command.
+    pub fn destroy(self, log: impl AsRef<str>) -> Result<(), ZfsError> {
+        let log = cstring(log)?;
+        let rc = unsafe { libzfs::zpool_destroy(self.handle, log.as_ptr()) };
+        ZfsError::from_rc(rc).result(())
+    }
+
+    /// Export the pool. With `force` the pool is exported even if its datasets are busy.
+    pub fn export(self, force: bool, log: impl AsRef<str>) -> Result<(), ZfsError> {
+        let log = cstring(log)?;
+        let rc = unsafe { libzfs::zpool_export(self.handle, force, log.as_ptr()) };
+        ZfsError::from_rc(rc).result(())
+    }
+
+    /// Property value as formatted by libzfs. With `literal` numbers are not converted
+    /// to human readable form.
+    pub fn string_property(
+        &self,
+        property: zpool_prop_t,
+        literal: bool,
+    ) -> Result<String, ZfsError> {
+        let mut buf = vec![0 as libc::c_char; libzfs::ZPOOL_MAXPROPLEN as usize];
+        let rc = unsafe {
+            libzfs::zpool_get_prop(self.handle, property, buf.as_mut_ptr(), buf.len(), literal)
+        };
+        if rc != 0 {
+            return Err(ZfsError::from_rc(rc));
+        }
+        let value = unsafe { ffi::CStr::from_ptr(buf.as_ptr()) };
+        Ok(value.to_string_lossy().into_owned())
+    }
+}
+
+impl From<*mut libzfs::zpool_handle_t> for ZpoolHandle {
+    fn from(handle: *mut libzfs::zpool_handle_t) -> Self {
+        Self { handle }
+    }
+}
+
+impl Drop for ZpoolHandle {
+    fn drop(&mut self) {
+        unsafe { libzfs::zpool_close(self.handle) };
+    }
+}
+
+/// All the imported zpools
+pub fn zpool_list() -> Result<Vec<ZpoolHandle>, ZfsError> {
+    let mut pools: Vec<*mut libzfs::zpool_handle_t> = vec![];
+    let ptr = &mut pools as *mut _ as *mut libc::c_void;
+    let rc = unsafe { libzfs::zpool_iter(Some(zpool_list_cb), ptr) };
+    let pools = pools.into_iter().map(ZpoolHandle::from).collect();
+    ZfsError::from_rc(rc).result(pools)
+}
+
+/// Create new pool with `nvroot` vdev tree, the pool `props` and root filesystem `fsprops`
+pub fn zpool_create(
+    name: impl AsRef<str>,
+    nvroot: impl Into<nvpair::NvList>,
+    props: impl Into<nvpair::NvList>,
+    fsprops: impl Into<nvpair::NvList>,
+) -> Result<(), ZfsError> {
+    let name = cstring(name)?;
+    let nvroot = nvroot.into();
+    let props = props.into();
+    let fsprops = fsprops.into();
+    let rc = unsafe { libzfs::zpool_create(name.as_ptr(), *nvroot, *props, *fsprops) };
+    ZfsError::from_rc(rc).result(())
+}
+
+/// Decode ZPOOL_CONFIG_VDEV_STATS array
+pub fn vdev_stat(array: &[u64]) -> libzfs::vdev_stat_t {
+    unsafe { libzfs::stat_from_array(array) }
+}
+
+/// Decode ZPOOL_CONFIG_SCAN_STATS array
+pub fn pool_scan_stat(array: &[u64]) -> libzfs::pool_scan_stat_t {
+    unsafe { libzfs::stat_from_array(array) }
+}
+
+pub fn zpool_prop_to_name(property: zpool_prop_t) -> Cow<'static, str> {
+    unsafe {
+        let cstr = libzfs::zpool_prop_to_name(property);
+        ffi::CStr::from_ptr(cstr).to_string_lossy()
+    }
+}
+
+unsafe extern "C" fn zpool_list_cb(
+    handle: *mut libzfs::zpool_handle_t,
+    ptr: *mut libc::c_void,
+) -> libc::c_int {
+    let pools = &mut *(ptr as *mut Vec<*mut libzfs::zpool_handle_t>);
+    pools.push(handle);
+
+    0
+}
@@ -51,6 +51,129 @@ impl From<nvpair::NvListError> for LzcError {
     }
 }
 
+/// Error returned by pool operations that act on a set of vdevs (initialize, trim).
+/// Besides the overall error code it carries the error reported for each vdev, keyed by vdev guid.
+///
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct VdevErrors {
+    pub error: LzcError,
+    pub vdevs: Vec<(u64, LzcError)>,
+}
+
+impl VdevErrors {
+    pub(crate) fn err(
+        code: libc::c_int,
+        errlist: Option<nvpair::NvList>,
+        key: &str,
+    ) -> Result<(), Self> {
+        let error = match LzcError::err(code) {
+            Ok(()) => return Ok(()),
+            Err(error) => error,
+        };
+        let vdevs = errlist
+            .as_ref()
+            .and_then(|errlist| errlist.lookup_nvpair(key).ok().flatten())
+            .map(|nvp| {
+                nvp.nvlist()
+                    .items()
+                    .filter_map(|(name, value)| {
+                        let guid = name.parse().ok()?;
+                        let code = match value {
+                            nvpair::Value::I64(code) => code as libc::c_int,
+                            _ => return None,
+                        };
+                        Some((guid, LzcError { code }))
+                    })
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        Err(Self { error, vdevs })
+    }
+}
+
+impl fmt::Display for VdevErrors {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.error.fmt(f)?;
+        for (guid, error) in &self.vdevs {
+            write!(f, "; vdev {guid}: {error}")?;
+        }
+        Ok(())
+    }
+}
+
+impl ::std::error::Error for VdevErrors {}
+
+impl From<LzcError> for VdevErrors {
+    fn from(error: LzcError) -> Self {
+        let vdevs = Vec::new();
+        Self { error, vdevs }
+    }
+}
+
+impl From<ffi::NulError> for VdevErrors {
+    fn from(e: ffi::NulError) -> Self {
+        LzcError::from(e).into()
+    }
+}
+
+impl From<nvpair::NvListError> for VdevErrors {
+    fn from(e: nvpair::NvListError) -> Self {
+        LzcError::from(e).into()
+    }
+}
+
+/// Error returned by channel programs. Carries the error message reported by the
+/// Lua interpreter (if any).
+///
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ChannelProgramError {
+    pub error: LzcError,
+    pub message: Option<String>,
+}
+
+impl ChannelProgramError {
+    pub(crate) fn err(code: libc::c_int, outnvl: Option<&nvpair::NvList>) -> Result<(), Self> {
+        let error = match LzcError::err(code) {
+            Ok(()) => return Ok(()),
+            Err(error) => error,
+        };
+        let message = outnvl
+            .and_then(|outnvl| outnvl.lookup_nvpair("error").ok().flatten())
+            .and_then(|nvp| match nvp.value() {
+                nvpair::Value::String(message) => Some(message),
+                _ => None,
+            });
+
+        Err(Self { error, message })
+    }
+}
+
+impl fmt::Display for ChannelProgramError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.error.fmt(f)?;
+        if let Some(ref message) = self.message {
+            write!(f, ": {message}")?;
+        }
+        Ok(())
+    }
+}
+
+impl ::std::error::Error for ChannelProgramError {}
+
+impl From<LzcError> for ChannelProgramError {
+    fn from(error: LzcError) -> Self {
+        let message = None;
+        Self { error, message }
+    }
+}
+
+impl From<ffi::NulError> for ChannelProgramError {
+    fn from(e: ffi::NulError) -> Self {
+        LzcError::from(e).into()
+    }
+}
+
 fn libc_strerror(code: i32) -> Cow<'static, str> {
     unsafe {
         let cstr = libc::strerror(code);
@@ -23,9 +23,30 @@ use std::ptr;
 use razor_libzfscore as lzc;
 use razor_nvpair as nvpair;
 
+pub use error::ChannelProgramError;
 pub use error::LzcError;
+pub use error::VdevErrors;
+pub use pool::checkpoint_pool;
+pub use pool::discard_pool_checkpoint;
+pub use pool::initialize;
+pub use pool::reopen_pool;
+pub use pool::trim;
+pub use pool::InitializeAction;
+pub use pool::TrimAction;
+#[cfg(feature = "wait")]
+pub use wait::{wait, wait_fs, wait_tag, Activity, FsActivity};
+#[cfg(feature = "tokio")]
+pub use wait::{wait_async, wait_fs_async};
+pub use zcp::channel_program;
+pub use zcp::channel_program_nosync;
+pub use zcp::ZCP_DEFAULT_INSTRLIMIT;
+pub use zcp::ZCP_DEFAULT_MEMLIMIT;
 
 mod error;
+mod pool;
+#[cfg(feature = "wait")]
+mod wait;
+mod zcp;
 
 /// Create new ZFS filesystem
 ///
@@ -125,7 +146,7 @@ where
         | lzc::lzc_send_flags::LZC_SEND_FLAG_COMPRESS;
     let code = unsafe {
         let source = source.as_ptr();
-        let from = from.map_or(ptr::null(), |from| from.as_ptr());
+        let from = from.as_ref().map_or(ptr::null(), |from| from.as_ptr());
         let fd = file.as_raw_fd();
         lzc::lzc_send(source, from, fd, flags)
     };
@@ -154,12 +175,33 @@ where
         | lzc::lzc_send_flags::LZC_SEND_FLAG_COMPRESS;
     let code = unsafe {
         let source = source.as_ptr();
-        let from = from.map_or(ptr::null(), |from| from.as_ptr());
+        let from = from.as_ref().map_or(ptr::null(), |from| from.as_ptr());
         lzc::lzc_send_resume(source, from, fd, flags, resumeobj, resumeoff)
     };
     LzcError::err(code)
 }
 
+/// Estimate the size of the send stream
+///
+pub fn send_space<S, F>(source: S, from: Option<F>) -> Result<u64, LzcError>
+where
+    S: AsRef<str>,
+    F: AsRef<str>,
+{
+    let source = cstring(source)?;
+    let from = from.map(cstring).transpose()?;
+    let flags = lzc::lzc_send_flags::LZC_SEND_FLAG_EMBED_DATA
+        | lzc::lzc_send_flags::LZC_SEND_FLAG_LARGE_BLOCK
+        | lzc::lzc_send_flags::LZC_SEND_FLAG_COMPRESS;
+    let mut space = 0;
+    let code = unsafe {
+        let source = source.as_ptr();
+        let from = from.as_ref().map_or(ptr::null(), |from| from.as_ptr());
+        lzc::lzc_send_space(source, from, flags, &mut space)
+    };
+    LzcError::err(code).map(|_| space)
+}
+
 /// Receive
 ///
 pub fn receive<S, O, U>(
@@ -180,7 +222,9 @@ where
     let fd = file.as_raw_fd();
     let code = unsafe {
         let snapname = snapname.as_ptr();
-        let origin = origin.map_or(ptr::null(), |origin| origin.as_ptr());
+        let origin = origin
+            .as_ref()
+            .map_or(ptr::null(), |origin| origin.as_ptr());
         lzc::lzc_receive(snapname, *props, origin, force, raw, fd)
     };
     LzcError::err(code)
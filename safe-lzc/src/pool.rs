@@ -0,0 +1,139 @@
+use super::*;
+
+const ZPOOL_INITIALIZE_VDEVS: &str = "initialize_vdevs";
+const ZPOOL_TRIM_VDEVS: &str = "trim_vdevs";
+
+/// Action for `initialize()`
+///
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum InitializeAction {
+    Start,
+    Cancel,
+    Suspend,
+}
+
+impl From<InitializeAction> for lzc::pool_initialize_func_t {
+    fn from(action: InitializeAction) -> Self {
+        match action {
+            InitializeAction::Start => Self::POOL_INITIALIZE_START,
+            InitializeAction::Cancel => Self::POOL_INITIALIZE_CANCEL,
+            InitializeAction::Suspend => Self::POOL_INITIALIZE_SUSPEND,
+        }
+    }
+}
+
+/// Action for `trim()`
+///
+/// `rate` is the TRIM rate in bytes per second, `0` means "as fast as possible".
+/// `secure` requests a secure TRIM, which is not supported by all devices.
+///
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TrimAction {
+    Start { rate: u64, secure: bool },
+    Cancel,
+    Suspend,
+}
+
+impl TrimAction {
+    /// Start full speed non-secure TRIM
+    ///
+    pub fn start() -> Self {
+        Self::Start {
+            rate: 0,
+            secure: false,
+        }
+    }
+
+    fn func(&self) -> lzc::pool_trim_func_t {
+        match self {
+            Self::Start { .. } => lzc::pool_trim_func_t::POOL_TRIM_START,
+            Self::Cancel => lzc::pool_trim_func_t::POOL_TRIM_CANCEL,
+            Self::Suspend => lzc::pool_trim_func_t::POOL_TRIM_SUSPEND,
+        }
+    }
+
+    fn rate(&self) -> u64 {
+        match self {
+            Self::Start { rate, .. } => *rate,
+            _ => 0,
+        }
+    }
+
+    fn secure(&self) -> bool {
+        match self {
+            Self::Start { secure, .. } => *secure,
+            _ => false,
+        }
+    }
+}
+
+/// Start, cancel or suspend initialization of the given leaf vdevs (by guid) in the named zpool
+///
+pub fn initialize(
+    pool: impl AsRef<str>,
+    action: InitializeAction,
+    vdevs: impl IntoIterator<Item = u64>,
+) -> Result<(), VdevErrors> {
+    let pool = cstring(pool)?;
+    let vdevs = vdev_guids(vdevs)?;
+    let mut errlist = ptr::null_mut();
+    let code = unsafe { lzc::lzc_initialize(pool.as_ptr(), action.into(), *vdevs, &mut errlist) };
+    let errlist = (!errlist.is_null()).then(|| nvpair::NvList::from(errlist));
+    VdevErrors::err(code, errlist, ZPOOL_INITIALIZE_VDEVS)
+}
+
+/// Start, cancel or suspend TRIM of the given leaf vdevs (by guid) in the named zpool
+///
+pub fn trim(
+    pool: impl AsRef<str>,
+    action: TrimAction,
+    vdevs: impl IntoIterator<Item = u64>,
+) -> Result<(), VdevErrors> {
+    let pool = cstring(pool)?;
+    let vdevs = vdev_guids(vdevs)?;
+    let mut errlist = ptr::null_mut();
+    let code = unsafe {
+        lzc::lzc_trim(
+            pool.as_ptr(),
+            action.func(),
+            action.rate(),
+            action.secure(),
+            *vdevs,
+            &mut errlist,
+        )
+    };
+    let errlist = (!errlist.is_null()).then(|| nvpair::NvList::from(errlist));
+    VdevErrors::err(code, errlist, ZPOOL_TRIM_VDEVS)
+}
+
+/// Create checkpoint for the named zpool
+///
+pub fn checkpoint_pool(pool: impl AsRef<str>) -> Result<(), LzcError> {
+    let pool = cstring(pool)?;
+    let code = unsafe { lzc::lzc_pool_checkpoint(pool.as_ptr()) };
+    LzcError::err(code)
+}
+
+/// Discard checkpoint of the named zpool
+///
+pub fn discard_pool_checkpoint(pool: impl AsRef<str>) -> Result<(), LzcError> {
+    let pool = cstring(pool)?;
+    let code = unsafe { lzc::lzc_pool_checkpoint_discard(pool.as_ptr()) };
+    LzcError::err(code)
+}
+
+/// Reopen all the vdevs of the named zpool, optionally restarting in-progress scrub
+///
+pub fn reopen_pool(pool: impl AsRef<str>, scrub_restart: bool) -> Result<(), LzcError> {
+    let pool = cstring(pool)?;
+    let code = unsafe { lzc::lzc_reopen(pool.as_ptr(), scrub_restart) };
+    LzcError::err(code)
+}
+
+fn vdev_guids(vdevs: impl IntoIterator<Item = u64>) -> Result<nvpair::NvList, LzcError> {
+    let mut nvl = nvpair::NvList::new();
+    for guid in vdevs {
+        nvl.add_uint64(guid.to_string(), guid)?;
+    }
+    Ok(nvl)
+}
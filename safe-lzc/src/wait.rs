@@ -0,0 +1,107 @@
+use razor_libnvpair as libnvpair;
+
+use super::*;
+
+/// Pool activity to wait for
+///
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Activity {
+    CheckpointDiscard,
+    Free,
+    Initialize,
+    Replace,
+    Remove,
+    Resilver,
+    Scrub,
+    Trim,
+}
+
+impl From<Activity> for lzc::zpool_wait_activity_t {
+    fn from(activity: Activity) -> Self {
+        match activity {
+            Activity::CheckpointDiscard => Self::ZPOOL_WAIT_CKPT_DISCARD,
+            Activity::Free => Self::ZPOOL_WAIT_FREE,
+            Activity::Initialize => Self::ZPOOL_WAIT_INITIALIZE,
+            Activity::Replace => Self::ZPOOL_WAIT_REPLACE,
+            Activity::Remove => Self::ZPOOL_WAIT_REMOVE,
+            Activity::Resilver => Self::ZPOOL_WAIT_RESILVER,
+            Activity::Scrub => Self::ZPOOL_WAIT_SCRUB,
+            Activity::Trim => Self::ZPOOL_WAIT_TRIM,
+        }
+    }
+}
+
+/// Dataset activity to wait for
+///
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum FsActivity {
+    DeleteQueue,
+}
+
+impl From<FsActivity> for lzc::zfs_wait_activity_t {
+    fn from(activity: FsActivity) -> Self {
+        match activity {
+            FsActivity::DeleteQueue => Self::ZFS_WAIT_DELETEQ,
+        }
+    }
+}
+
+/// Wait until the given activity is no longer in progress in the named zpool.
+/// Returns `true` if actually had to wait, `false` if activity was not in progress.
+///
+pub fn wait(pool: impl AsRef<str>, activity: Activity) -> Result<bool, LzcError> {
+    let pool = cstring(pool)?;
+    let mut waited = libnvpair::boolean_t::B_FALSE;
+    let code = unsafe { lzc::lzc_wait(pool.as_ptr(), activity.into(), &mut waited) };
+    LzcError::err(code).map(|_| waited == libnvpair::boolean_t::B_TRUE)
+}
+
+/// Same as `wait()`, but only waits for the activity identified by `tag`
+/// (for instance, particular vdev initialization).
+///
+pub fn wait_tag(pool: impl AsRef<str>, activity: Activity, tag: u64) -> Result<bool, LzcError> {
+    let pool = cstring(pool)?;
+    let mut waited = libnvpair::boolean_t::B_FALSE;
+    let code = unsafe { lzc::lzc_wait_tag(pool.as_ptr(), activity.into(), tag, &mut waited) };
+    LzcError::err(code).map(|_| waited == libnvpair::boolean_t::B_TRUE)
+}
+
+/// Wait until the given activity is no longer in progress in the named filesystem.
+/// Returns `true` if actually had to wait, `false` if activity was not in progress.
+///
+pub fn wait_fs(fs: impl AsRef<str>, activity: FsActivity) -> Result<bool, LzcError> {
+    let fs = cstring(fs)?;
+    let mut waited = libnvpair::boolean_t::B_FALSE;
+    let code = unsafe { lzc::lzc_wait_fs(fs.as_ptr(), activity.into(), &mut waited) };
+    LzcError::err(code).map(|_| waited == libnvpair::boolean_t::B_TRUE)
+}
+
+/// Async version of `wait()`. Blocks in a dedicated tokio blocking thread.
+///
+#[cfg(feature = "tokio")]
+pub async fn wait_async(pool: impl AsRef<str>, activity: Activity) -> Result<bool, LzcError> {
+    let pool = pool.as_ref().to_string();
+    blocking(move || wait(pool, activity)).await
+}
+
+/// Async version of `wait_fs()`. Blocks in a dedicated tokio blocking thread.
+///
+#[cfg(feature = "tokio")]
+pub async fn wait_fs_async(fs: impl AsRef<str>, activity: FsActivity) -> Result<bool, LzcError> {
+    let fs = fs.as_ref().to_string();
+    blocking(move || wait_fs(fs, activity)).await
+}
+
+#[cfg(feature = "tokio")]
+async fn blocking<F>(f: F) -> Result<bool, LzcError>
+where
+    F: FnOnce() -> Result<bool, LzcError> + Send + 'static,
+{
+    match tokio::task::spawn_blocking(f).await {
+        Ok(result) => result,
+        Err(e) if e.is_panic() => std::panic::resume_unwind(e.into_panic()),
+        Err(_) => Err(LzcError {
+            code: libc::ECANCELED,
+        }),
+    }
+}
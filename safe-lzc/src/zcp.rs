@@ -0,0 +1,67 @@
+use super::*;
+
+/// Default instruction limit for channel programs (same as `zfs program`)
+pub const ZCP_DEFAULT_INSTRLIMIT: u64 = 10 * 1000 * 1000;
+/// Default memory limit for channel programs (same as `zfs program`)
+pub const ZCP_DEFAULT_MEMLIMIT: u64 = 10 * 1024 * 1024;
+
+/// Run ZFS channel program `program` (Lua source) against the named zpool.
+/// All the changes made by the program are applied atomically in a single txg.
+/// `args` is passed to the program as its only argument.
+/// Returns the output nvlist, the program's return value is under the "return" key.
+///
+pub fn channel_program(
+    pool: impl AsRef<str>,
+    program: impl AsRef<str>,
+    args: impl nvpair::ToNvList,
+    instr_limit: u64,
+    mem_limit: u64,
+) -> Result<nvpair::NvList, ChannelProgramError> {
+    run(pool, program, args, instr_limit, mem_limit, true)
+}
+
+/// Same as `channel_program()`, but runs the program without syncing a txg.
+/// Such program is not allowed to make any changes.
+///
+pub fn channel_program_nosync(
+    pool: impl AsRef<str>,
+    program: impl AsRef<str>,
+    args: impl nvpair::ToNvList,
+    instr_limit: u64,
+    mem_limit: u64,
+) -> Result<nvpair::NvList, ChannelProgramError> {
+    run(pool, program, args, instr_limit, mem_limit, false)
+}
+
+fn run(
+    pool: impl AsRef<str>,
+    program: impl AsRef<str>,
+    args: impl nvpair::ToNvList,
+    instr_limit: u64,
+    mem_limit: u64,
+    sync: bool,
+) -> Result<nvpair::NvList, ChannelProgramError> {
+    let pool = cstring(pool)?;
+    let program = cstring(program)?;
+    let mut outnvl = ptr::null_mut();
+    let code = unsafe {
+        let pool = pool.as_ptr();
+        let program = program.as_ptr();
+        let args = args.to_nvlist();
+        if sync {
+            lzc::lzc_channel_program(pool, program, instr_limit, mem_limit, args, &mut outnvl)
+        } else {
+            lzc::lzc_channel_program_nosync(
+                pool,
+                program,
+                instr_limit,
+                mem_limit,
+                args,
+                &mut outnvl,
+            )
+        }
+    };
+    let outnvl = (!outnvl.is_null()).then(|| nvpair::NvList::from(outnvl));
+    ChannelProgramError::err(code, outnvl.as_ref())?;
+    Ok(outnvl.unwrap_or_default())
+}
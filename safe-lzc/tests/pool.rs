@@ -0,0 +1,32 @@
+use razor_safe_lzc as lzc;
+
+const NO_SUCH_POOL: &str = "razor-no-such-pool";
+
+#[test]
+fn checkpoint_non_existing_pool() {
+    let e = lzc::checkpoint_pool(NO_SUCH_POOL).unwrap_err();
+    assert_eq!(e.code, libc::ENOENT);
+    let e = lzc::discard_pool_checkpoint(NO_SUCH_POOL).unwrap_err();
+    assert_eq!(e.code, libc::ENOENT);
+}
+
+#[test]
+fn trim_non_existing_pool() {
+    let e = lzc::trim(NO_SUCH_POOL, lzc::TrimAction::start(), [1]).unwrap_err();
+    assert_eq!(e.error.code, libc::ENOENT);
+    assert!(e.vdevs.is_empty());
+}
+
+#[test]
+fn initialize_non_existing_pool() {
+    let e = lzc::initialize(NO_SUCH_POOL, lzc::InitializeAction::Start, [1]).unwrap_err();
+    assert_eq!(e.error.code, libc::ENOENT);
+    assert!(e.vdevs.is_empty());
+}
+
+#[cfg(feature = "wait")]
+#[test]
+fn wait_non_existing_pool() {
+    let e = lzc::wait(NO_SUCH_POOL, lzc::Activity::Scrub).unwrap_err();
+    assert_eq!(e.code, libc::ENOENT);
+}
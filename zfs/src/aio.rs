@@ -0,0 +1,177 @@
+//! Async (tokio) send and receive, and the ZFS event stream.
+//!
+//! Both `send()` and `receive()` own a pipe between the async caller and the blocking
+//! `lzc` call running in a tokio blocking thread. Dropping the send stream (or the receive future)
+//! closes the pipe, which makes the blocking call fail and terminate.
+//!
+//! `events()` reads the events in a dedicated thread. A blocked `zpool_events_next()` cannot
+//! be interrupted, so the thread polls the queue instead and exits within the poll interval
+//! once the stream is dropped.
+//!
+
+use std::future::Future;
+use std::io;
+use std::pin::Pin;
+use std::task::{ready, Context, Poll};
+use std::thread;
+use std::time::Duration;
+
+use tokio::io::{AsyncRead, ReadBuf};
+use tokio::net::unix::pipe::{Receiver, Sender};
+use tokio::sync::mpsc;
+use tokio::task::{self, JoinHandle};
+use tokio_stream::wrappers::ReceiverStream;
+use tokio_stream::Stream;
+
+use zpool::event::{Events, ZfsEvent};
+
+use super::*;
+
+/// Start sending `source` (incremental from `from`, if specified).
+/// Returns the send stream as `AsyncRead`. Errors from the underlying send are reported
+/// by the stream itself once the pipe is drained.
+///
+/// Must be called from within tokio runtime.
+///
+pub fn send<S, F>(source: S, from: Option<F>) -> Result<SendStream>
+where
+    S: AsRef<str>,
+    F: AsRef<str>,
+{
+    let source = source.as_ref().to_string();
+    let from = from.map(|from| from.as_ref().to_string());
+    let (reader, writer) = pipe::create()?;
+    let reader = Receiver::from_owned_fd(reader)?;
+    let sender = task::spawn_blocking(move || Zfs::send(source, from, writer));
+    let sender = Some(sender);
+
+    Ok(SendStream { reader, sender })
+}
+
+/// Receive `input` stream into `snapname`.
+/// Resolves once the receive is complete.
+///
+pub async fn receive<S, O, R>(snapname: S, origin: Option<O>, force: bool, input: R) -> Result<()>
+where
+    S: AsRef<str>,
+    O: AsRef<str>,
+    R: AsyncRead + Unpin,
+{
+    let mut input = input;
+    let snapname = snapname.as_ref().to_string();
+    let origin = origin.map(|origin| origin.as_ref().to_string());
+    let (reader, writer) = pipe::create()?;
+    let mut writer = Sender::from_owned_fd(writer)?;
+    let receiver = task::spawn_blocking(move || Zfs::receive(snapname, origin, force, reader));
+
+    let copied = tokio::io::copy(&mut input, &mut writer).await;
+    drop(writer);
+    let received = join(receiver).await;
+
+    match copied {
+        // Receiver is gone, its own error is more relevant
+        Err(e) if e.kind() == io::ErrorKind::BrokenPipe => received,
+        Err(e) => Err(e.into()),
+        Ok(_) => received,
+    }
+}
+
+/// ZFS send stream. Yields the stream data, and reports the send error (if any) in place of EOF.
+///
+#[derive(Debug)]
+pub struct SendStream {
+    reader: Receiver,
+    sender: Option<JoinHandle<Result<()>>>,
+}
+
+impl AsyncRead for SendStream {
+    fn poll_read(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut ReadBuf<'_>,
+    ) -> Poll<io::Result<()>> {
+        if buf.remaining() == 0 {
+            return Poll::Ready(Ok(()));
+        }
+
+        let filled = buf.filled().len();
+        ready!(Pin::new(&mut self.reader).poll_read(cx, buf))?;
+        if buf.filled().len() > filled {
+            return Poll::Ready(Ok(()));
+        }
+
+        // EOF - check how the sender is doing
+        if let Some(sender) = self.sender.as_mut() {
+            let result = ready!(Pin::new(sender).poll(cx));
+            self.sender = None;
+            joined(result).map_err(io::Error::other)?;
+        }
+
+        Poll::Ready(Ok(()))
+    }
+}
+
+/// Follow the ZFS events, as `zpool events -f` does. `events` are usually created with
+/// `skip_existing()` to get only the new events.
+///
+pub fn events(events: Events) -> EventStream {
+    let (tx, rx) = mpsc::channel(EVENT_BACKLOG);
+    let reader = thread::spawn(move || {
+        let mut events = events.follow(false);
+        while !tx.is_closed() {
+            match events.next() {
+                Some(event) => {
+                    let failed = event.is_err();
+                    if tx.blocking_send(event).is_err() || failed {
+                        break;
+                    }
+                }
+                None => thread::sleep(EVENT_POLL_INTERVAL),
+            }
+        }
+    });
+    let events = ReceiverStream::new(rx);
+
+    EventStream { events, reader }
+}
+
+const EVENT_BACKLOG: usize = 64;
+const EVENT_POLL_INTERVAL: Duration = Duration::from_millis(100);
+
+/// Async stream of ZFS events
+///
+#[derive(Debug)]
+pub struct EventStream {
+    events: ReceiverStream<Result<ZfsEvent>>,
+    reader: thread::JoinHandle<()>,
+}
+
+impl EventStream {
+    /// Stop following the events and wait for the reader thread to exit
+    pub fn close(self) {
+        drop(self.events);
+        if let Err(panic) = self.reader.join() {
+            std::panic::resume_unwind(panic);
+        }
+    }
+}
+
+impl Stream for EventStream {
+    type Item = Result<ZfsEvent>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        Pin::new(&mut self.events).poll_next(cx)
+    }
+}
+
+async fn join<T>(handle: JoinHandle<Result<T>>) -> Result<T> {
+    joined(handle.await)
+}
+
+fn joined<T>(result: Result<Result<T>, task::JoinError>) -> Result<T> {
+    match result {
+        Ok(result) => result,
+        Err(e) if e.is_panic() => std::panic::resume_unwind(e.into_panic()),
+        Err(_) => Err(DatasetError::Unknown(libc::ECANCELED)),
+    }
+}
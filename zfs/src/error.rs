@@ -14,8 +14,26 @@ pub enum DatasetError {
     NvListError(#[from] NvListError),
     #[error("Snapshot name must contain @ ({0})")]
     InvalidSnapshotName(String),
+    #[error("dataset to snapshot is excluded ({0})")]
+    ExcludedDataset(String),
+    #[error("invalid vdev specification: {0}")]
+    InvalidVdevSpec(String),
+    #[error("pool not found ({0})")]
+    PoolNotFound(String),
+    #[error("more than one matching pool ({0})")]
+    AmbiguousPool(String),
+    #[error("device busy ({0})")]
+    DeviceBusy(String),
+    #[error("operation would reduce redundancy ({0})")]
+    ReducedRedundancy(String),
+    #[error("devices have different sector alignment (ashift) ({0})")]
+    AshiftMismatch(String),
+    #[error("unsupported dataset type ({0})")]
+    UnsupportedDatasetType(String),
     #[error(transparent)]
     CoreErr(#[from] libzfs::ZfsError),
+    #[error(transparent)]
+    ChannelProgram(#[from] lzc::ChannelProgramError),
     #[error("unknown builder error, error code: ({0})")]
     Unknown(i32),
 }
@@ -28,6 +46,38 @@ impl DatasetError {
     pub fn invalid_snapshot_name(name: impl AsRef<str>) -> Self {
         Self::InvalidSnapshotName(name.as_ref().to_string())
     }
+
+    pub fn excluded_dataset(name: impl AsRef<str>) -> Self {
+        Self::ExcludedDataset(name.as_ref().to_string())
+    }
+
+    pub fn pool_not_found(pool: impl AsRef<str>) -> Self {
+        Self::PoolNotFound(pool.as_ref().to_string())
+    }
+
+    pub fn ambiguous_pool(pool: impl AsRef<str>) -> Self {
+        Self::AmbiguousPool(pool.as_ref().to_string())
+    }
+
+    pub fn device_busy(device: impl AsRef<str>) -> Self {
+        Self::DeviceBusy(device.as_ref().to_string())
+    }
+
+    pub fn reduced_redundancy(reason: impl AsRef<str>) -> Self {
+        Self::ReducedRedundancy(reason.as_ref().to_string())
+    }
+
+    pub fn ashift_mismatch(device: impl AsRef<str>) -> Self {
+        Self::AshiftMismatch(device.as_ref().to_string())
+    }
+
+    pub fn unsupported_dataset_type(name: impl AsRef<str>) -> Self {
+        Self::UnsupportedDatasetType(name.as_ref().to_string())
+    }
+
+    pub fn invalid_vdev_spec(reason: impl AsRef<str>) -> Self {
+        Self::InvalidVdevSpec(reason.as_ref().to_string())
+    }
 }
 
 impl From<io::Error> for DatasetError {
@@ -21,16 +21,28 @@ use razor_safe_lzc as lzc;
 pub use nvpair::NvListError;
 
 pub use error::DatasetError;
+pub use progress::Progress;
+pub use progress::Transfer;
 pub use zfs::Bookmark;
+pub use zfs::Dataset;
 pub use zfs::Filesystem;
 pub use zfs::FilesystemBuilder;
+pub use zfs::MountOptions;
 pub use zfs::Snapshot;
 pub use zfs::SnapshotBuilder;
 pub use zfs::Volume;
 pub use zfs::VolumeBuilder;
 pub use zfs::Zfs;
+pub use zpool::Zpool;
 
+#[cfg(feature = "aio")]
+pub mod aio;
 mod error;
+mod pipe;
+pub mod progress;
+pub mod stream;
+pub mod zcp;
 pub mod zfs;
+pub mod zpool;
 
 pub type Result<T, E = DatasetError> = std::result::Result<T, E>;
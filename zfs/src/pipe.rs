@@ -0,0 +1,18 @@
+use std::io;
+use std::os::unix::io::{AsRawFd, FromRawFd, OwnedFd};
+
+const PIPE_SIZE: libc::c_int = 1024 * 1024;
+
+/// Create pipe with both ends blocking. Returns (reader, writer) pair.
+///
+#[allow(unsafe_code)]
+pub(crate) fn create() -> io::Result<(OwnedFd, OwnedFd)> {
+    let mut fds = [0; 2];
+    if unsafe { libc::pipe2(fds.as_mut_ptr(), libc::O_CLOEXEC) } < 0 {
+        return Err(io::Error::last_os_error());
+    }
+    let (reader, writer) = unsafe { (OwnedFd::from_raw_fd(fds[0]), OwnedFd::from_raw_fd(fds[1])) };
+    // Best effort, pipe works regardless, but larger one is much faster
+    let _size = unsafe { libc::fcntl(writer.as_raw_fd(), libc::F_SETPIPE_SZ, PIPE_SIZE) };
+    Ok((reader, writer))
+}
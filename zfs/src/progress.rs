@@ -0,0 +1,258 @@
+//! Send and receive with progress reporting and bandwidth limiting.
+//!
+//! `Transfer` interposes a pipe between the caller's stream and the blocking `lzc` call
+//! running in a dedicated thread, so the data flowing through can be counted and throttled.
+//!
+
+use std::fmt;
+use std::fs::File;
+use std::io::{self, Read, Write};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use super::*;
+
+const BUFFER_SIZE: usize = 128 * 1024;
+const DEFAULT_INTERVAL: Duration = Duration::from_secs(1);
+
+type Callback<'a> = Box<dyn FnMut(&Progress) + 'a>;
+
+/// Snapshot of transfer progress, passed to the progress callback
+///
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Progress {
+    /// Bytes transferred so far
+    pub bytes: u64,
+    /// Estimated total size of the stream, if known
+    pub total: Option<u64>,
+    /// Time since the transfer started
+    pub elapsed: Duration,
+    /// `true` for the final report, once the stream is drained
+    pub done: bool,
+}
+
+impl Progress {
+    /// Average transfer rate in bytes per second
+    ///
+    pub fn rate(&self) -> u64 {
+        let elapsed = self.elapsed.as_secs_f64();
+        if elapsed > 0.0 {
+            (self.bytes as f64 / elapsed) as u64
+        } else {
+            0
+        }
+    }
+
+    /// Estimated time to completion, based on the average rate so far
+    ///
+    pub fn eta(&self) -> Option<Duration> {
+        let total = self.total?;
+        let rate = self.rate();
+        if rate == 0 {
+            return None;
+        }
+        let remaining = total.saturating_sub(self.bytes);
+        Some(Duration::from_secs_f64(remaining as f64 / rate as f64))
+    }
+
+    /// Completion percentage (0.0 - 100.0), if the total is known
+    ///
+    pub fn percent(&self) -> Option<f64> {
+        match self.total {
+            Some(0) => Some(100.0),
+            Some(total) => Some((self.bytes as f64 / total as f64 * 100.0).min(100.0)),
+            None => None,
+        }
+    }
+}
+
+/// Progress-aware send/receive builder
+///
+pub struct Transfer<'a> {
+    progress: Option<Callback<'a>>,
+    interval: Duration,
+    limit: u64,
+    total: Option<u64>,
+}
+
+impl fmt::Debug for Transfer<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Transfer")
+            .field("progress", &self.progress.is_some())
+            .field("interval", &self.interval)
+            .field("limit", &self.limit)
+            .field("total", &self.total)
+            .finish()
+    }
+}
+
+impl Default for Transfer<'_> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<'a> Transfer<'a> {
+    pub fn new() -> Self {
+        Self {
+            progress: None,
+            interval: DEFAULT_INTERVAL,
+            limit: 0,
+            total: None,
+        }
+    }
+
+    /// Callback invoked every `interval` and once more when the transfer is complete
+    ///
+    pub fn progress(mut self, progress: impl FnMut(&Progress) + 'a) -> Self {
+        self.progress = Some(Box::new(progress));
+        self
+    }
+
+    /// Minimal interval between progress reports (default is 1 second)
+    ///
+    pub fn interval(mut self, interval: Duration) -> Self {
+        self.interval = interval;
+        self
+    }
+
+    /// Limit bandwidth to `bytes_per_sec`, `0` means unlimited (default)
+    ///
+    pub fn limit(mut self, bytes_per_sec: u64) -> Self {
+        self.limit = bytes_per_sec;
+        self
+    }
+
+    /// Expected total size of the stream. For send it is estimated automatically
+    /// unless set explicitly.
+    ///
+    pub fn total(mut self, total: u64) -> Self {
+        self.total = Some(total);
+        self
+    }
+
+    /// Send `source` (incremental from `from`, if specified) into `output`.
+    /// Returns the number of bytes sent.
+    ///
+    pub fn send<S, F, W>(mut self, source: S, from: Option<F>, output: W) -> Result<u64>
+    where
+        S: AsRef<str>,
+        F: AsRef<str>,
+        W: Write,
+    {
+        let source = source.as_ref();
+        let from = from.as_ref().map(F::as_ref);
+        if self.total.is_none() {
+            self.total = lzc::send_space(source, from).ok();
+        }
+
+        let (reader, writer) = pipe::create()?;
+        thread::scope(|scope| {
+            let sender = scope.spawn(move || Zfs::send(source, from, writer));
+            let (bytes, copied) = self.copy(File::from(reader), output);
+            let sent = join(sender.join());
+            // Sender fails with EPIPE once output is gone, the output error is the real cause
+            copied?;
+            sent.map(|_| bytes)
+        })
+    }
+
+    /// Receive `input` stream into `snapname`.
+    /// Returns the number of bytes received.
+    ///
+    pub fn receive<S, O, R>(
+        mut self,
+        snapname: S,
+        origin: Option<O>,
+        force: bool,
+        input: R,
+    ) -> Result<u64>
+    where
+        S: AsRef<str>,
+        O: AsRef<str>,
+        R: Read,
+    {
+        let snapname = snapname.as_ref();
+        let origin = origin.as_ref().map(O::as_ref);
+
+        let (reader, writer) = pipe::create()?;
+        thread::scope(|scope| {
+            let receiver = scope.spawn(move || Zfs::receive(snapname, origin, force, reader));
+            let (bytes, copied) = self.copy(input, File::from(writer));
+            let received = join(receiver.join());
+            match copied {
+                // EPIPE means the receiver is gone, either done with the stream or failed
+                // on its own, its result decides
+                Err(e) if e.kind() != io::ErrorKind::BrokenPipe => Err(e.into()),
+                _ => received.map(|_| bytes),
+            }
+        })
+    }
+
+    // Both `reader` and `writer` are consumed, so that the pipe end is closed
+    // by the time the peer thread is joined.
+    // Returns the bytes written so far along with the result, as it is needed on error too.
+    fn copy(&mut self, reader: impl Read, writer: impl Write) -> (u64, io::Result<()>) {
+        let mut bytes = 0;
+        let result = self.copy_counted(reader, writer, &mut bytes);
+        (bytes, result)
+    }
+
+    fn copy_counted(
+        &mut self,
+        mut reader: impl Read,
+        mut writer: impl Write,
+        bytes: &mut u64,
+    ) -> io::Result<()> {
+        let chunk = match self.limit {
+            0 => BUFFER_SIZE,
+            limit => usize::try_from(limit).map_or(BUFFER_SIZE, |limit| limit.min(BUFFER_SIZE)),
+        };
+        let mut buf = vec![0; chunk];
+        let start = Instant::now();
+        let mut reported = start;
+
+        loop {
+            let n = match reader.read(&mut buf) {
+                Ok(0) => break,
+                Ok(n) => n,
+                Err(e) if e.kind() == io::ErrorKind::Interrupted => continue,
+                Err(e) => return Err(e),
+            };
+            writer.write_all(&buf[..n])?;
+            *bytes += n as u64;
+
+            if self.limit > 0 {
+                let expected = Duration::from_secs_f64(*bytes as f64 / self.limit as f64);
+                if let Some(ahead) = expected.checked_sub(start.elapsed()) {
+                    thread::sleep(ahead);
+                }
+            }
+
+            if reported.elapsed() >= self.interval {
+                reported = Instant::now();
+                self.report(*bytes, start.elapsed(), false);
+            }
+        }
+
+        writer.flush()?;
+        self.report(*bytes, start.elapsed(), true);
+        Ok(())
+    }
+
+    fn report(&mut self, bytes: u64, elapsed: Duration, done: bool) {
+        let total = self.total;
+        if let Some(progress) = self.progress.as_mut() {
+            progress(&Progress {
+                bytes,
+                total,
+                elapsed,
+                done,
+            });
+        }
+    }
+}
+
+fn join<T>(result: thread::Result<Result<T>>) -> Result<T> {
+    result.unwrap_or_else(|e| std::panic::resume_unwind(e))
+}
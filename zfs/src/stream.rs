@@ -0,0 +1,245 @@
+//! Pure-Rust ZFS send stream parser, the equivalent of `zstream dump`.
+//!
+//! `StreamReader` walks the stream record by record, decoding every `dmu_replay_record`
+//! and verifying the running fletcher-4 checksum. Both little and big endian streams are
+//! supported, as well as compound (replication) streams.
+//!
+//! `StreamWriter` writes (possibly modified) records back, recomputing the checksums. It powers
+//! the stream transformations - `split()`, `verify()` and `retarget()`.
+//!
+
+use std::io::{self, Read};
+
+use thiserror::Error;
+
+use super::*;
+
+pub use checksum::Checksum;
+pub use record::Begin;
+pub use record::ByteOrder;
+pub use record::DdtKey;
+pub use record::End;
+pub use record::Features;
+pub use record::Free;
+pub use record::FreeObjects;
+pub use record::Header;
+pub use record::Object;
+pub use record::ObjectRange;
+pub use record::Record;
+pub use record::RecordType;
+pub use record::Redact;
+pub use record::Spill;
+pub use record::Write;
+pub use record::WriteByRef;
+pub use record::WriteEmbedded;
+pub use record::BACKUP_MAGIC;
+pub use record::RECORD_SIZE;
+pub use stats::RecordStats;
+pub use stats::Stats;
+pub use transform::retarget;
+pub use transform::split;
+pub use transform::verify;
+pub use writer::StreamWriter;
+
+use record::CHECKSUM_OFFSET;
+
+mod checksum;
+mod record;
+mod stats;
+mod transform;
+mod writer;
+
+#[derive(Debug, Error)]
+pub enum StreamError {
+    #[error(transparent)]
+    Io(#[from] io::Error),
+    #[error("invalid send stream magic {0:#x}")]
+    InvalidMagic(u64),
+    #[error("unknown record type {record_type} at offset {offset}")]
+    UnknownRecord { record_type: u32, offset: u64 },
+    #[error("stream truncated at offset {0}")]
+    Truncated(u64),
+    #[error(
+        "{record_type} checksum mismatch at offset {offset}: expected {expected}, got {actual}"
+    )]
+    ChecksumMismatch {
+        record_type: RecordType,
+        offset: u64,
+        expected: Checksum,
+        actual: Checksum,
+    },
+    #[error("unexpected {record_type} record at offset {offset}")]
+    UnexpectedRecord {
+        record_type: RecordType,
+        offset: u64,
+    },
+    #[error("invalid stream name {0:?}")]
+    InvalidName(String),
+    #[error("compound stream cannot be retargeted, split it first")]
+    Compound,
+}
+
+/// Send stream reader. Yields stream records one by one, stops at the first error.
+///
+#[derive(Debug)]
+pub struct StreamReader<R> {
+    input: R,
+    order: Option<ByteOrder>,
+    checksum: Checksum,
+    offset: u64,
+    verify: bool,
+    done: bool,
+}
+
+impl<R: Read> StreamReader<R> {
+    pub fn new(input: R) -> Self {
+        Self {
+            input,
+            order: None,
+            checksum: Checksum::default(),
+            offset: 0,
+            verify: true,
+            done: false,
+        }
+    }
+
+    /// Whether to verify stream checksums (default is `true`).
+    /// Disabling it allows inspecting corrupted streams.
+    ///
+    pub fn verify(mut self, verify: bool) -> Self {
+        self.verify = verify;
+        self
+    }
+
+    /// Number of bytes consumed so far
+    ///
+    pub fn offset(&self) -> u64 {
+        self.offset
+    }
+
+    /// Byte order of the stream, known once the first record is read
+    ///
+    pub fn byte_order(&self) -> Option<ByteOrder> {
+        self.order
+    }
+
+    /// Read next record. Returns `None` at the end of the stream.
+    ///
+    pub fn next_record(&mut self) -> Result<Option<Record>, StreamError> {
+        if self.done {
+            return Ok(None);
+        }
+        let record = self.read_record();
+        self.done = !matches!(record, Ok(Some(_)));
+        record
+    }
+
+    fn read_record(&mut self) -> Result<Option<Record>, StreamError> {
+        let offset = self.offset;
+        let mut raw = Box::new([0; RECORD_SIZE]);
+        match read_exact(&mut self.input, &mut raw[..])? {
+            0 => return Ok(None),
+            RECORD_SIZE => (),
+            _ => return Err(StreamError::Truncated(offset)),
+        }
+
+        let order = match self.order {
+            Some(order) => order,
+            None => {
+                let order = ByteOrder::detect(&raw[..])
+                    .ok_or_else(|| StreamError::InvalidMagic(ByteOrder::Little.u64(&raw[8..])))?;
+                *self.order.insert(order)
+            }
+        };
+
+        let header =
+            Header::decode(&raw, order).map_err(|record_type| StreamError::UnknownRecord {
+                record_type,
+                offset,
+            })?;
+        let record_type = header.record_type();
+
+        // Every record (but BEGIN) carries the checksum of everything preceding it,
+        // END also carries the checksum of the whole (sub)stream preceding it.
+        let previous = self.checksum;
+        self.checksum.update(&raw[..CHECKSUM_OFFSET], order);
+        let expected = Checksum::decode(&raw[CHECKSUM_OFFSET..], order);
+        if self.verify
+            && record_type != RecordType::Begin
+            && !expected.is_zero()
+            && expected != self.checksum
+        {
+            return Err(StreamError::ChecksumMismatch {
+                record_type,
+                offset,
+                expected,
+                actual: self.checksum,
+            });
+        }
+        self.checksum.update(&raw[CHECKSUM_OFFSET..], order);
+        if let Header::End(end) = &header {
+            if self.verify && end.checksum != previous {
+                return Err(StreamError::ChecksumMismatch {
+                    record_type,
+                    offset,
+                    expected: end.checksum,
+                    actual: previous,
+                });
+            }
+        }
+
+        let size = header.payload_size();
+        let mut payload = Vec::new();
+        let read = (&mut self.input).take(size).read_to_end(&mut payload)?;
+        if read as u64 != size {
+            return Err(StreamError::Truncated(offset));
+        }
+        self.checksum.update(&payload, order);
+        self.offset += (RECORD_SIZE + read) as u64;
+
+        // Each substream is checksummed separately
+        if record_type == RecordType::End {
+            self.checksum = Checksum::default();
+        }
+
+        Ok(Some(Record {
+            header,
+            raw,
+            payload,
+            order,
+            offset,
+        }))
+    }
+}
+
+impl<R: Read> Iterator for StreamReader<R> {
+    type Item = Result<Record, StreamError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.next_record().transpose()
+    }
+}
+
+/// Read the whole stream verifying its checksums, return its statistics summary
+///
+pub fn dump(input: impl Read) -> Result<Stats, StreamError> {
+    let mut stats = Stats::new();
+    for record in StreamReader::new(input) {
+        stats.add(&record?);
+    }
+    Ok(stats)
+}
+
+// Like `Read::read_exact()`, but tells clean EOF (nothing read) from truncated buffer
+fn read_exact(input: &mut impl Read, buf: &mut [u8]) -> io::Result<usize> {
+    let mut filled = 0;
+    while filled < buf.len() {
+        match input.read(&mut buf[filled..]) {
+            Ok(0) => break,
+            Ok(n) => filled += n,
+            Err(e) if e.kind() == io::ErrorKind::Interrupted => continue,
+            Err(e) => return Err(e),
+        }
+    }
+    Ok(filled)
+}
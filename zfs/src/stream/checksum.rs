@@ -0,0 +1,51 @@
+use std::fmt;
+
+use super::*;
+
+/// Fletcher-4 checksum (`zio_cksum_t`) as used by send streams.
+/// Doubles as the running checksum state while the stream is being read.
+///
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct Checksum(pub [u64; 4]);
+
+impl Checksum {
+    pub fn is_zero(&self) -> bool {
+        self.0 == [0; 4]
+    }
+
+    /// Feed `data` into the running checksum. Data is treated as a sequence of 32 bit words
+    /// in the stream byte order; trailing bytes that do not form a full word are ignored.
+    ///
+    pub fn update(&mut self, data: &[u8], order: ByteOrder) {
+        let [mut a, mut b, mut c, mut d] = self.0;
+        for word in data.chunks_exact(4) {
+            a = a.wrapping_add(u64::from(order.u32(word)));
+            b = b.wrapping_add(a);
+            c = c.wrapping_add(b);
+            d = d.wrapping_add(c);
+        }
+        self.0 = [a, b, c, d];
+    }
+
+    pub(crate) fn encode(&self, bytes: &mut [u8], order: ByteOrder) {
+        for (word, value) in self.0.iter().enumerate() {
+            order.put_u64(&mut bytes[word * 8..], *value);
+        }
+    }
+
+    pub(crate) fn decode(bytes: &[u8], order: ByteOrder) -> Self {
+        Self([
+            order.u64(bytes),
+            order.u64(&bytes[8..]),
+            order.u64(&bytes[16..]),
+            order.u64(&bytes[24..]),
+        ])
+    }
+}
+
+impl fmt::Display for Checksum {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let [a, b, c, d] = self.0;
+        write!(f, "{a:x}/{b:x}/{c:x}/{d:x}")
+    }
+}
@@ -0,0 +1,681 @@
+use std::fmt;
+
+use super::*;
+
+/// Size of on-wire `dmu_replay_record_t`
+pub const RECORD_SIZE: usize = 312;
+/// Offset of the trailing `drr_checksum` within the record
+pub(crate) const CHECKSUM_OFFSET: usize = RECORD_SIZE - 32;
+/// `DMU_BACKUP_MAGIC`
+pub const BACKUP_MAGIC: u64 = 0x2f5bacbac;
+
+// Offsets within `drr_u`
+const DRR_UNION_OFFSET: usize = 8;
+const DRR_BEGIN_FROMGUID_OFFSET: usize = 40;
+const DRR_BEGIN_TONAME_OFFSET: usize = 48;
+const DRR_END_CHECKSUM_OFFSET: usize = 0;
+const MAXNAMELEN: usize = 256;
+const DMU_COMPOUNDSTREAM: u64 = 0x2;
+
+/// Byte order of the send stream, as determined by the magic of its first BEGIN record
+///
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ByteOrder {
+    Little,
+    Big,
+}
+
+impl ByteOrder {
+    pub(crate) fn detect(record: &[u8]) -> Option<Self> {
+        let magic = &record[8..16];
+        if Self::Little.u64(magic) == BACKUP_MAGIC {
+            Some(Self::Little)
+        } else if Self::Big.u64(magic) == BACKUP_MAGIC {
+            Some(Self::Big)
+        } else {
+            None
+        }
+    }
+
+    pub(crate) fn u32(self, bytes: &[u8]) -> u32 {
+        let mut buf = [0; 4];
+        buf.copy_from_slice(&bytes[..4]);
+        match self {
+            Self::Little => u32::from_le_bytes(buf),
+            Self::Big => u32::from_be_bytes(buf),
+        }
+    }
+
+    pub(crate) fn u64(self, bytes: &[u8]) -> u64 {
+        let mut buf = [0; 8];
+        buf.copy_from_slice(&bytes[..8]);
+        match self {
+            Self::Little => u64::from_le_bytes(buf),
+            Self::Big => u64::from_be_bytes(buf),
+        }
+    }
+
+    pub(crate) fn put_u64(self, bytes: &mut [u8], value: u64) {
+        let buf = match self {
+            Self::Little => value.to_le_bytes(),
+            Self::Big => value.to_be_bytes(),
+        };
+        bytes[..8].copy_from_slice(&buf);
+    }
+}
+
+/// `dmu_replay_record` type
+///
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum RecordType {
+    Begin,
+    Object,
+    FreeObjects,
+    Write,
+    Free,
+    End,
+    WriteByRef,
+    Spill,
+    WriteEmbedded,
+    ObjectRange,
+    Redact,
+}
+
+impl RecordType {
+    pub const ALL: [Self; 11] = [
+        Self::Begin,
+        Self::Object,
+        Self::FreeObjects,
+        Self::Write,
+        Self::Free,
+        Self::End,
+        Self::WriteByRef,
+        Self::Spill,
+        Self::WriteEmbedded,
+        Self::ObjectRange,
+        Self::Redact,
+    ];
+
+    /// Name as used by OpenZFS, e.g. `DRR_WRITE`
+    ///
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Begin => "DRR_BEGIN",
+            Self::Object => "DRR_OBJECT",
+            Self::FreeObjects => "DRR_FREEOBJECTS",
+            Self::Write => "DRR_WRITE",
+            Self::Free => "DRR_FREE",
+            Self::End => "DRR_END",
+            Self::WriteByRef => "DRR_WRITE_BYREF",
+            Self::Spill => "DRR_SPILL",
+            Self::WriteEmbedded => "DRR_WRITE_EMBEDDED",
+            Self::ObjectRange => "DRR_OBJECT_RANGE",
+            Self::Redact => "DRR_REDACT",
+        }
+    }
+}
+
+impl TryFrom<u32> for RecordType {
+    type Error = u32;
+
+    fn try_from(value: u32) -> Result<Self, Self::Error> {
+        Self::ALL.get(value as usize).copied().ok_or(value)
+    }
+}
+
+impl fmt::Display for RecordType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.name().fmt(f)
+    }
+}
+
+/// Send stream feature flags (`DMU_BACKUP_FEATURE_*`)
+///
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct Features(pub u64);
+
+impl Features {
+    pub const DEDUP: Self = Self(1 << 0);
+    pub const DEDUPPROPS: Self = Self(1 << 1);
+    pub const SA_SPILL: Self = Self(1 << 2);
+    pub const EMBED_DATA: Self = Self(1 << 16);
+    pub const LZ4: Self = Self(1 << 17);
+    pub const LARGE_BLOCKS: Self = Self(1 << 19);
+    pub const RESUMING: Self = Self(1 << 20);
+    pub const REDACTED: Self = Self(1 << 21);
+    pub const COMPRESSED: Self = Self(1 << 22);
+    pub const LARGE_DNODE: Self = Self(1 << 23);
+    pub const RAW: Self = Self(1 << 24);
+    pub const ZSTD: Self = Self(1 << 25);
+    pub const HOLDS: Self = Self(1 << 26);
+
+    pub fn contains(&self, other: Self) -> bool {
+        self.0 & other.0 == other.0
+    }
+}
+
+impl fmt::LowerHex for Features {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::LowerHex::fmt(&self.0, f)
+    }
+}
+
+/// Deduplication key (`ddt_key_t`)
+///
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct DdtKey {
+    pub checksum: Checksum,
+    pub prop: u64,
+}
+
+/// `drr_begin`
+///
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Begin {
+    pub magic: u64,
+    pub versioninfo: u64,
+    pub creation_time: u64,
+    pub objset_type: u32,
+    pub flags: u32,
+    pub toguid: u64,
+    pub fromguid: u64,
+    pub toname: String,
+    pub payloadlen: u32,
+}
+
+impl Begin {
+    /// Stream header type, `DMU_SUBSTREAM` or `DMU_COMPOUNDSTREAM`
+    ///
+    pub fn hdrtype(&self) -> u64 {
+        self.versioninfo & 0x3
+    }
+
+    /// Feature flags required to receive this stream
+    ///
+    pub fn features(&self) -> Features {
+        Features((self.versioninfo >> 2) & ((1 << 30) - 1))
+    }
+
+    /// Whether this is the header of a compound (replication) stream
+    ///
+    pub fn is_compound(&self) -> bool {
+        self.hdrtype() == DMU_COMPOUNDSTREAM
+    }
+}
+
+/// `drr_end`
+///
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct End {
+    pub checksum: Checksum,
+    pub toguid: u64,
+}
+
+/// `drr_object`
+///
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Object {
+    pub object: u64,
+    pub r#type: u32,
+    pub bonustype: u32,
+    pub blksz: u32,
+    pub bonuslen: u32,
+    pub checksumtype: u8,
+    pub compress: u8,
+    pub dn_slots: u8,
+    pub flags: u8,
+    pub raw_bonuslen: u32,
+    pub toguid: u64,
+    pub indblkshift: u8,
+    pub nlevels: u8,
+    pub nblkptr: u8,
+    pub maxblkid: u64,
+}
+
+/// `drr_freeobjects`
+///
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct FreeObjects {
+    pub firstobj: u64,
+    pub numobjs: u64,
+    pub toguid: u64,
+}
+
+/// `drr_write`
+///
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Write {
+    pub object: u64,
+    pub r#type: u32,
+    pub offset: u64,
+    pub logical_size: u64,
+    pub toguid: u64,
+    pub checksumtype: u8,
+    pub flags: u8,
+    pub compressiontype: u8,
+    pub key: DdtKey,
+    pub compressed_size: u64,
+    pub salt: [u8; 8],
+    pub iv: [u8; 12],
+    pub mac: [u8; 16],
+}
+
+/// `drr_free`
+///
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Free {
+    pub object: u64,
+    pub offset: u64,
+    pub length: u64,
+    pub toguid: u64,
+}
+
+/// `drr_write_byref`
+///
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct WriteByRef {
+    pub object: u64,
+    pub offset: u64,
+    pub length: u64,
+    pub toguid: u64,
+    pub refguid: u64,
+    pub refobject: u64,
+    pub refoffset: u64,
+    pub checksumtype: u8,
+    pub flags: u8,
+    pub key: DdtKey,
+}
+
+/// `drr_spill`
+///
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Spill {
+    pub object: u64,
+    pub length: u64,
+    pub toguid: u64,
+    pub flags: u8,
+    pub compressiontype: u8,
+    pub compressed_size: u64,
+    pub salt: [u8; 8],
+    pub iv: [u8; 12],
+    pub mac: [u8; 16],
+    pub r#type: u32,
+}
+
+/// `drr_write_embedded`
+///
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct WriteEmbedded {
+    pub object: u64,
+    pub offset: u64,
+    pub length: u64,
+    pub toguid: u64,
+    pub compression: u8,
+    pub etype: u8,
+    pub lsize: u32,
+    pub psize: u32,
+}
+
+/// `drr_object_range`
+///
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ObjectRange {
+    pub firstobj: u64,
+    pub numslots: u64,
+    pub toguid: u64,
+    pub salt: [u8; 8],
+    pub iv: [u8; 12],
+    pub mac: [u8; 16],
+    pub flags: u8,
+}
+
+/// `drr_redact`
+///
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Redact {
+    pub object: u64,
+    pub offset: u64,
+    pub length: u64,
+    pub toguid: u64,
+}
+
+/// Decoded `dmu_replay_record` header
+///
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Header {
+    Begin(Begin),
+    Object(Object),
+    FreeObjects(FreeObjects),
+    Write(Write),
+    Free(Free),
+    End(End),
+    WriteByRef(WriteByRef),
+    Spill(Spill),
+    WriteEmbedded(WriteEmbedded),
+    ObjectRange(ObjectRange),
+    Redact(Redact),
+}
+
+impl Header {
+    pub(crate) fn decode(record: &[u8; RECORD_SIZE], order: ByteOrder) -> Result<Self, u32> {
+        let record_type = RecordType::try_from(order.u32(record))?;
+        let payloadlen = order.u32(&record[4..]);
+        let drr = Fields {
+            buf: &record[DRR_UNION_OFFSET..],
+            order,
+        };
+
+        let header = match record_type {
+            RecordType::Begin => Self::Begin(Begin {
+                magic: drr.u64(0),
+                versioninfo: drr.u64(8),
+                creation_time: drr.u64(16),
+                objset_type: drr.u32(24),
+                flags: drr.u32(28),
+                toguid: drr.u64(32),
+                fromguid: drr.u64(40),
+                toname: drr.string(DRR_BEGIN_TONAME_OFFSET),
+                payloadlen,
+            }),
+            RecordType::Object => Self::Object(Object {
+                object: drr.u64(0),
+                r#type: drr.u32(8),
+                bonustype: drr.u32(12),
+                blksz: drr.u32(16),
+                bonuslen: drr.u32(20),
+                checksumtype: drr.u8(24),
+                compress: drr.u8(25),
+                dn_slots: drr.u8(26),
+                flags: drr.u8(27),
+                raw_bonuslen: drr.u32(28),
+                toguid: drr.u64(32),
+                indblkshift: drr.u8(40),
+                nlevels: drr.u8(41),
+                nblkptr: drr.u8(42),
+                maxblkid: drr.u64(48),
+            }),
+            RecordType::FreeObjects => Self::FreeObjects(FreeObjects {
+                firstobj: drr.u64(0),
+                numobjs: drr.u64(8),
+                toguid: drr.u64(16),
+            }),
+            RecordType::Write => Self::Write(Write {
+                object: drr.u64(0),
+                r#type: drr.u32(8),
+                offset: drr.u64(16),
+                logical_size: drr.u64(24),
+                toguid: drr.u64(32),
+                checksumtype: drr.u8(40),
+                flags: drr.u8(41),
+                compressiontype: drr.u8(42),
+                key: drr.ddt_key(48),
+                compressed_size: drr.u64(88),
+                salt: drr.bytes(96),
+                iv: drr.bytes(104),
+                mac: drr.bytes(116),
+            }),
+            RecordType::Free => Self::Free(Free {
+                object: drr.u64(0),
+                offset: drr.u64(8),
+                length: drr.u64(16),
+                toguid: drr.u64(24),
+            }),
+            RecordType::End => Self::End(End {
+                checksum: drr.checksum(0),
+                toguid: drr.u64(32),
+            }),
+            RecordType::WriteByRef => Self::WriteByRef(WriteByRef {
+                object: drr.u64(0),
+                offset: drr.u64(8),
+                length: drr.u64(16),
+                toguid: drr.u64(24),
+                refguid: drr.u64(32),
+                refobject: drr.u64(40),
+                refoffset: drr.u64(48),
+                checksumtype: drr.u8(56),
+                flags: drr.u8(57),
+                key: drr.ddt_key(64),
+            }),
+            RecordType::Spill => Self::Spill(Spill {
+                object: drr.u64(0),
+                length: drr.u64(8),
+                toguid: drr.u64(16),
+                flags: drr.u8(24),
+                compressiontype: drr.u8(25),
+                compressed_size: drr.u64(32),
+                salt: drr.bytes(40),
+                iv: drr.bytes(48),
+                mac: drr.bytes(60),
+                r#type: drr.u32(76),
+            }),
+            RecordType::WriteEmbedded => Self::WriteEmbedded(WriteEmbedded {
+                object: drr.u64(0),
+                offset: drr.u64(8),
+                length: drr.u64(16),
+                toguid: drr.u64(24),
+                compression: drr.u8(32),
+                etype: drr.u8(33),
+                lsize: drr.u32(40),
+                psize: drr.u32(44),
+            }),
+            RecordType::ObjectRange => Self::ObjectRange(ObjectRange {
+                firstobj: drr.u64(0),
+                numslots: drr.u64(8),
+                toguid: drr.u64(16),
+                salt: drr.bytes(24),
+                iv: drr.bytes(32),
+                mac: drr.bytes(44),
+                flags: drr.u8(60),
+            }),
+            RecordType::Redact => Self::Redact(Redact {
+                object: drr.u64(0),
+                offset: drr.u64(8),
+                length: drr.u64(16),
+                toguid: drr.u64(24),
+            }),
+        };
+
+        Ok(header)
+    }
+
+    pub fn record_type(&self) -> RecordType {
+        match self {
+            Self::Begin(_) => RecordType::Begin,
+            Self::Object(_) => RecordType::Object,
+            Self::FreeObjects(_) => RecordType::FreeObjects,
+            Self::Write(_) => RecordType::Write,
+            Self::Free(_) => RecordType::Free,
+            Self::End(_) => RecordType::End,
+            Self::WriteByRef(_) => RecordType::WriteByRef,
+            Self::Spill(_) => RecordType::Spill,
+            Self::WriteEmbedded(_) => RecordType::WriteEmbedded,
+            Self::ObjectRange(_) => RecordType::ObjectRange,
+            Self::Redact(_) => RecordType::Redact,
+        }
+    }
+
+    /// Size of the payload following this record header
+    ///
+    pub fn payload_size(&self) -> u64 {
+        match self {
+            Self::Begin(begin) => u64::from(begin.payloadlen),
+            Self::Object(object) if object.raw_bonuslen != 0 => u64::from(object.raw_bonuslen),
+            Self::Object(object) => roundup8(u64::from(object.bonuslen)),
+            Self::Write(write) if write.compressiontype != 0 => write.compressed_size,
+            Self::Write(write) => write.logical_size,
+            Self::Spill(spill) if spill.compressed_size != 0 => spill.compressed_size,
+            Self::Spill(spill) => spill.length,
+            Self::WriteEmbedded(embedded) => roundup8(u64::from(embedded.psize)),
+            Self::FreeObjects(_)
+            | Self::Free(_)
+            | Self::End(_)
+            | Self::WriteByRef(_)
+            | Self::ObjectRange(_)
+            | Self::Redact(_) => 0,
+        }
+    }
+}
+
+/// Single send stream record - the header and its payload (if any)
+///
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Record {
+    pub(crate) header: Header,
+    pub(crate) raw: Box<[u8; RECORD_SIZE]>,
+    pub(crate) payload: Vec<u8>,
+    pub(crate) order: ByteOrder,
+    pub(crate) offset: u64,
+}
+
+impl Record {
+    pub fn header(&self) -> &Header {
+        &self.header
+    }
+
+    pub fn record_type(&self) -> RecordType {
+        self.header.record_type()
+    }
+
+    pub fn payload(&self) -> &[u8] {
+        &self.payload
+    }
+
+    pub fn byte_order(&self) -> ByteOrder {
+        self.order
+    }
+
+    /// Offset of this record within the stream
+    ///
+    pub fn offset(&self) -> u64 {
+        self.offset
+    }
+
+    /// Size of this record in the stream, including the payload
+    ///
+    pub fn size(&self) -> u64 {
+        (RECORD_SIZE + self.payload.len()) as u64
+    }
+
+    /// Stream checksum stored in this record header (zero for BEGIN records)
+    ///
+    pub fn checksum(&self) -> Checksum {
+        match self.header {
+            Header::Begin(_) => Checksum::default(),
+            _ => Checksum::decode(&self.raw[CHECKSUM_OFFSET..], self.order),
+        }
+    }
+
+    /// Rewrite the destination name of the BEGIN record
+    ///
+    pub fn set_toname(&mut self, toname: impl AsRef<str>) -> Result<(), StreamError> {
+        let toname = toname.as_ref();
+        if toname.len() >= MAXNAMELEN || toname.contains('\0') {
+            return Err(StreamError::InvalidName(toname.to_string()));
+        }
+        let begin = self.begin_mut()?;
+        begin.toname = toname.to_string();
+        let field = &mut self.raw[DRR_UNION_OFFSET + DRR_BEGIN_TONAME_OFFSET..];
+        field.fill(0);
+        field[..toname.len()].copy_from_slice(toname.as_bytes());
+        Ok(())
+    }
+
+    /// Rewrite the guid of the incremental source snapshot of the BEGIN record
+    ///
+    pub fn set_fromguid(&mut self, fromguid: u64) -> Result<(), StreamError> {
+        let order = self.order;
+        self.begin_mut()?.fromguid = fromguid;
+        order.put_u64(
+            &mut self.raw[DRR_UNION_OFFSET + DRR_BEGIN_FROMGUID_OFFSET..],
+            fromguid,
+        );
+        Ok(())
+    }
+
+    // Encode the record with the stream checksums recomputed from the `checksum` of everything
+    // preceding it. Returns the header to be written, followed by the payload.
+    pub(crate) fn encode(&self, checksum: &mut Checksum) -> [u8; RECORD_SIZE] {
+        let mut raw = *self.raw;
+        if let Header::End(_) = self.header {
+            checksum.encode(
+                &mut raw[DRR_UNION_OFFSET + DRR_END_CHECKSUM_OFFSET..],
+                self.order,
+            );
+        }
+        checksum.update(&raw[..CHECKSUM_OFFSET], self.order);
+        if !matches!(self.header, Header::Begin(_)) {
+            checksum.encode(&mut raw[CHECKSUM_OFFSET..], self.order);
+        }
+        checksum.update(&raw[CHECKSUM_OFFSET..], self.order);
+        checksum.update(&self.payload, self.order);
+        raw
+    }
+
+    fn begin_mut(&mut self) -> Result<&mut Begin, StreamError> {
+        match &mut self.header {
+            Header::Begin(begin) => Ok(begin),
+            header => Err(StreamError::UnexpectedRecord {
+                record_type: header.record_type(),
+                offset: self.offset,
+            }),
+        }
+    }
+
+    /// Decode BEGIN record payload. Compound (replication) streams carry the description
+    /// of all the included filesystems and snapshots there.
+    ///
+    pub fn nvlist(&self) -> Result<Option<nvpair::NvList>, NvListError> {
+        match self.header {
+            Header::Begin(_) if !self.payload.is_empty() => {
+                nvpair::NvList::unpack(&self.payload).map(Some)
+            }
+            _ => Ok(None),
+        }
+    }
+}
+
+fn roundup8(size: u64) -> u64 {
+    (size + 7) & !7
+}
+
+struct Fields<'a> {
+    buf: &'a [u8],
+    order: ByteOrder,
+}
+
+impl Fields<'_> {
+    fn u8(&self, offset: usize) -> u8 {
+        self.buf[offset]
+    }
+
+    fn u32(&self, offset: usize) -> u32 {
+        self.order.u32(&self.buf[offset..])
+    }
+
+    fn u64(&self, offset: usize) -> u64 {
+        self.order.u64(&self.buf[offset..])
+    }
+
+    fn bytes<const N: usize>(&self, offset: usize) -> [u8; N] {
+        let mut bytes = [0; N];
+        bytes.copy_from_slice(&self.buf[offset..offset + N]);
+        bytes
+    }
+
+    fn checksum(&self, offset: usize) -> Checksum {
+        Checksum::decode(&self.buf[offset..], self.order)
+    }
+
+    fn ddt_key(&self, offset: usize) -> DdtKey {
+        DdtKey {
+            checksum: self.checksum(offset),
+            prop: self.u64(offset + 32),
+        }
+    }
+
+    fn string(&self, offset: usize) -> String {
+        let bytes = &self.buf[offset..];
+        let len = bytes.iter().position(|&b| b == 0).unwrap_or(bytes.len());
+        String::from_utf8_lossy(&bytes[..len]).into_owned()
+    }
+}
@@ -0,0 +1,84 @@
+use std::collections::BTreeMap;
+use std::fmt;
+
+use super::*;
+
+/// Count and total payload size of the records of a single type
+///
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct RecordStats {
+    pub count: u64,
+    pub payload: u64,
+}
+
+/// Send stream statistics summary, the same as printed by `zstream dump`
+///
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Stats {
+    records: BTreeMap<RecordType, RecordStats>,
+}
+
+impl Stats {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Account for `record`
+    ///
+    pub fn add(&mut self, record: &Record) {
+        let stats = self.records.entry(record.record_type()).or_default();
+        stats.count += 1;
+        stats.payload += record.payload().len() as u64;
+    }
+
+    /// Statistics for the records of the given type
+    ///
+    pub fn get(&self, record_type: RecordType) -> RecordStats {
+        self.records.get(&record_type).copied().unwrap_or_default()
+    }
+
+    /// Total number of records
+    ///
+    pub fn records(&self) -> u64 {
+        self.records.values().map(|stats| stats.count).sum()
+    }
+
+    /// Total size of all the payloads
+    ///
+    pub fn payload(&self) -> u64 {
+        self.records.values().map(|stats| stats.payload).sum()
+    }
+
+    /// Total size of all the record headers
+    ///
+    pub fn header_overhead(&self) -> u64 {
+        self.records() * RECORD_SIZE as u64
+    }
+
+    /// Total stream length
+    ///
+    pub fn length(&self) -> u64 {
+        self.payload() + self.header_overhead()
+    }
+}
+
+impl fmt::Display for Stats {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "SUMMARY:")?;
+        for record_type in RecordType::ALL {
+            let stats = self.get(record_type);
+            writeln!(
+                f,
+                "\tTotal {record_type} records = {} ({} bytes)",
+                stats.count, stats.payload
+            )?;
+        }
+        writeln!(f, "\tTotal records = {}", self.records())?;
+        let payload = self.payload();
+        writeln!(f, "\tTotal payload size = {payload} (0x{payload:x})")?;
+        let overhead = self.header_overhead();
+        writeln!(f, "\tTotal header overhead = {overhead} (0x{overhead:x})")?;
+        let length = self.length();
+        writeln!(f, "\tTotal stream length = {length} (0x{length:x})")
+    }
+}
@@ -0,0 +1,121 @@
+use std::io::{self, Read, Write};
+
+use super::*;
+
+/// Verify the stream without receiving it - all the record and END checksums, as well as
+/// the stream structure (every BEGIN is terminated by END). Returns the stream statistics.
+///
+pub fn verify(input: impl Read) -> Result<Stats, StreamError> {
+    let mut reader = StreamReader::new(input);
+    let mut stats = Stats::new();
+    let mut substream = false;
+
+    while let Some(record) = reader.next_record()? {
+        match record.record_type() {
+            // BEGIN inside of a substream means its END is missing
+            RecordType::Begin if substream => {
+                return Err(StreamError::UnexpectedRecord {
+                    record_type: RecordType::Begin,
+                    offset: record.offset(),
+                })
+            }
+            RecordType::Begin => substream = true,
+            // Compound stream header and trailer END records are outside of any substream
+            RecordType::End => substream = false,
+            _ if substream => (),
+            record_type => {
+                return Err(StreamError::UnexpectedRecord {
+                    record_type,
+                    offset: record.offset(),
+                })
+            }
+        }
+        stats.add(&record);
+    }
+
+    if substream || stats.records() == 0 {
+        return Err(StreamError::Truncated(reader.offset()));
+    }
+    Ok(stats)
+}
+
+/// Split compound (replication) stream into standalone per-snapshot substreams.
+/// `create` is called with the BEGIN record of each substream and returns its output.
+/// Returns the BEGIN records of all the substreams written.
+///
+/// Plain (non-compound) stream is passed through as is, as its only substream.
+///
+pub fn split<R, W, F>(input: R, mut create: F) -> Result<Vec<Begin>, StreamError>
+where
+    R: Read,
+    W: Write,
+    F: FnMut(&Begin) -> io::Result<W>,
+{
+    let mut substreams = Vec::new();
+    let mut output: Option<StreamWriter<W>> = None;
+
+    for record in StreamReader::new(input) {
+        let record = record?;
+        match (record.header(), output.as_mut()) {
+            (Header::Begin(begin), None) if begin.is_compound() => (),
+            (Header::Begin(begin), None) => {
+                let mut writer = StreamWriter::new(create(begin)?);
+                writer.write_record(&record)?;
+                output = Some(writer);
+                substreams.push(begin.clone());
+            }
+            (Header::End(_), Some(writer)) => {
+                writer.write_record(&record)?;
+                writer.flush()?;
+                output = None;
+            }
+            (Header::End(_), None) => (),
+            (Header::Begin(_), Some(_)) | (_, None) => {
+                return Err(StreamError::UnexpectedRecord {
+                    record_type: record.record_type(),
+                    offset: record.offset(),
+                })
+            }
+            (_, Some(writer)) => writer.write_record(&record)?,
+        }
+    }
+
+    match output {
+        Some(writer) => Err(StreamError::Truncated(writer.offset())),
+        None => Ok(substreams),
+    }
+}
+
+/// Retarget plain (non-compound) stream - rewrite its destination name and, optionally,
+/// the guid of the incremental source. Returns the number of bytes written.
+///
+pub fn retarget<R, W>(
+    input: R,
+    output: W,
+    toname: impl AsRef<str>,
+    fromguid: Option<u64>,
+) -> Result<u64, StreamError>
+where
+    R: Read,
+    W: Write,
+{
+    let toname = toname.as_ref();
+    let mut writer = StreamWriter::new(output);
+
+    for record in StreamReader::new(input) {
+        let mut record = record?;
+        if let Header::Begin(begin) = record.header() {
+            if begin.is_compound() {
+                return Err(StreamError::Compound);
+            }
+            record.set_toname(toname)?;
+            if let Some(fromguid) = fromguid {
+                record.set_fromguid(fromguid)?;
+            }
+        }
+        writer.write_record(&record)?;
+    }
+
+    writer.flush()?;
+    Ok(writer.offset())
+}
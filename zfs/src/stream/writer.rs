@@ -0,0 +1,50 @@
+use std::io::{self, Write};
+
+use super::*;
+
+/// Send stream writer. Records are written with their stream checksums recomputed,
+/// so that the output stays valid after records are modified, dropped or reordered.
+///
+#[derive(Debug)]
+pub struct StreamWriter<W> {
+    output: W,
+    checksum: Checksum,
+    offset: u64,
+}
+
+impl<W: Write> StreamWriter<W> {
+    pub fn new(output: W) -> Self {
+        Self {
+            output,
+            checksum: Checksum::default(),
+            offset: 0,
+        }
+    }
+
+    pub fn write_record(&mut self, record: &Record) -> io::Result<()> {
+        let raw = record.encode(&mut self.checksum);
+        self.output.write_all(&raw)?;
+        self.output.write_all(record.payload())?;
+        self.offset += record.size();
+
+        // Each substream is checksummed separately
+        if record.record_type() == RecordType::End {
+            self.checksum = Checksum::default();
+        }
+        Ok(())
+    }
+
+    /// Number of bytes written so far
+    ///
+    pub fn offset(&self) -> u64 {
+        self.offset
+    }
+
+    pub fn flush(&mut self) -> io::Result<()> {
+        self.output.flush()
+    }
+
+    pub fn into_inner(self) -> W {
+        self.output
+    }
+}
@@ -0,0 +1,82 @@
+//! Prebuilt ZFS channel programs (ZCP) for common batch operations.
+//! Every program runs atomically in a single txg - either all of its changes are applied
+//! or none of them.
+//!
+
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use super::*;
+
+/// Lua source of the atomic multi-snapshot program
+pub const SNAPSHOT: &str = include_str!("zcp/snapshot.lua");
+/// Lua source of the snapshot pruning program
+pub const DESTROY_SNAPSHOTS_BEFORE: &str = include_str!("zcp/destroy_snapshots_before.lua");
+
+/// Run arbitrary channel program with default limits against the named zpool.
+/// Returns the output nvlist, the program's return value (if any) is under the "return" key.
+///
+pub fn run(
+    pool: impl AsRef<str>,
+    program: impl AsRef<str>,
+    args: impl nvpair::ToNvList,
+) -> Result<nvpair::NvList> {
+    let output = lzc::channel_program(
+        pool,
+        program,
+        args,
+        lzc::ZCP_DEFAULT_INSTRLIMIT,
+        lzc::ZCP_DEFAULT_MEMLIMIT,
+    )?;
+    Ok(output)
+}
+
+/// Atomically create all the given snapshots in a single txg.
+/// All the snapshots must belong to the same zpool.
+///
+pub fn snapshot(snapshots: impl IntoIterator<Item = impl AsRef<str>>) -> Result<()> {
+    let snapshots = snapshots
+        .into_iter()
+        .map(|snapshot| snapshot.as_ref().to_string())
+        .collect::<Vec<_>>();
+    let pool = match snapshots.first() {
+        Some(snapshot) => pool_name(snapshot),
+        None => return Ok(()),
+    };
+    let mut args = nvpair::NvList::new();
+    args.add_string_array("snapshots", &snapshots)?;
+    run(pool, SNAPSHOT, args)?;
+    Ok(())
+}
+
+/// Atomically destroy all the snapshots of `dataset` created before `before`.
+/// With `recursive` also destroys such snapshots of all the descendant datasets.
+/// Returns the names of destroyed snapshots.
+///
+pub fn destroy_snapshots_before(
+    dataset: impl AsRef<str>,
+    before: SystemTime,
+    recursive: bool,
+) -> Result<Vec<String>> {
+    let dataset = dataset.as_ref();
+    let before = before
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |before| before.as_secs());
+    let mut args = nvpair::NvList::new();
+    args.add_string("dataset", dataset)?;
+    args.add_uint64("before", before)?;
+    args.add_boolean_value("recursive", recursive)?;
+    let output = run(pool_name(dataset), DESTROY_SNAPSHOTS_BEFORE, args)?;
+    let destroyed = match output.lookup_nvpair("return")? {
+        Some(nvp) if nvp.r#type() == nvpair::data_type_t::DATA_TYPE_NVLIST => nvp
+            .nvlist()
+            .items()
+            .map(|(snapshot, _creation)| snapshot)
+            .collect(),
+        _ => Vec::new(),
+    };
+    Ok(destroyed)
+}
+
+fn pool_name(dataset: &str) -> &str {
+    dataset.split(['/', '@', '#']).next().unwrap_or(dataset)
+}
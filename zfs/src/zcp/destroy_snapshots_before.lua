@@ -0,0 +1,40 @@
+-- Atomically destroy all the snapshots of args.dataset created before args.before
+-- (seconds since epoch), optionally descending into all its children.
+-- Nothing is destroyed unless every matching snapshot can be destroyed.
+-- Returns table of destroyed snapshots and their creation time.
+
+args = ...
+root = args["dataset"]
+before = args["before"]
+recursive = args["recursive"]
+
+candidates = {}
+
+function collect(dataset)
+    for snapshot in zfs.list.snapshots(dataset) do
+        creation = zfs.get_prop(snapshot, "creation")
+        if creation < before then
+            candidates[snapshot] = creation
+        end
+    end
+    if recursive then
+        for child in zfs.list.children(dataset) do
+            collect(child)
+        end
+    end
+end
+
+collect(root)
+
+for snapshot, _ in pairs(candidates) do
+    err = zfs.check.destroy(snapshot)
+    if err ~= 0 then
+        error("cannot destroy snapshot " .. snapshot .. ": error " .. err)
+    end
+end
+
+for snapshot, _ in pairs(candidates) do
+    zfs.sync.destroy(snapshot)
+end
+
+return candidates
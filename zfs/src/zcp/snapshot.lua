@@ -0,0 +1,16 @@
+-- Atomically create all the snapshots listed in args.snapshots.
+-- Nothing is created unless every snapshot can be created.
+
+args = ...
+snapshots = args["snapshots"]
+
+for _, snapshot in ipairs(snapshots) do
+    err = zfs.check.snapshot(snapshot)
+    if err ~= 0 then
+        error("cannot create snapshot " .. snapshot .. ": error " .. err)
+    end
+end
+
+for _, snapshot in ipairs(snapshots) do
+    zfs.sync.snapshot(snapshot)
+end
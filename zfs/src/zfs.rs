@@ -1,8 +1,11 @@
 use std::os::unix::io::AsRawFd;
 
 pub use dataset::Bookmark;
+pub use dataset::Dataset;
+pub use dataset::DatasetList;
 pub use dataset::Filesystem;
 pub use dataset::FilesystemBuilder;
+pub use dataset::MountOptions;
 pub use dataset::Snapshot;
 pub use dataset::SnapshotBuilder;
 pub use dataset::Volume;
@@ -49,12 +52,17 @@ impl Zfs {
         lzc::dataset_exists(dataset)
     }
 
-    pub fn list() -> libzfs::DatasetCollectorBuilder {
-        libzfs::zfs_list()
+    /// Open the dataset of any type
+    pub fn open(name: impl AsRef<str>) -> Result<Dataset> {
+        Dataset::open(name)
     }
 
-    pub fn list_from(name: impl AsRef<str>) -> libzfs::DatasetCollectorBuilder {
-        libzfs::zfs_list_from(name)
+    pub fn list() -> DatasetList {
+        DatasetList::new(libzfs::zfs_list())
+    }
+
+    pub fn list_from(name: impl AsRef<str>) -> DatasetList {
+        DatasetList::new(libzfs::zfs_list_from(name))
     }
 
     pub fn get_filesystem(name: impl AsRef<str>) -> Result<Filesystem> {
@@ -1,12 +1,14 @@
+use std::collections::BTreeMap;
 use std::ffi;
 
-use razor_nvpair as nvpair;
-
 use super::*;
 
 pub use bookmark::Bookmark;
 pub use filesystem::Filesystem;
 pub use filesystem::FilesystemBuilder;
+pub use filesystem::MountOptions;
+pub use generic::Dataset;
+pub use generic::DatasetList;
 pub use snapshot::Snapshot;
 pub use snapshot::SnapshotBuilder;
 pub use volume::Volume;
@@ -14,5 +16,6 @@ pub use volume::VolumeBuilder;
 
 mod bookmark;
 mod filesystem;
+mod generic;
 mod snapshot;
 mod volume;
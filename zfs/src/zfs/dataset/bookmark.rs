@@ -4,7 +4,7 @@ use libzfs::zfs_prop_t::*;
 
 #[derive(Debug)]
 pub struct Bookmark {
-    dataset: libzfs::ZfsHandle,
+    pub(super) dataset: libzfs::ZfsHandle,
 }
 
 impl Bookmark {
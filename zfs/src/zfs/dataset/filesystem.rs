@@ -1,3 +1,5 @@
+use std::path::PathBuf;
+
 use serde::ser::{Serialize, SerializeStruct, Serializer};
 
 use super::*;
@@ -7,7 +9,7 @@ use property::*;
 
 #[derive(Debug)]
 pub struct Filesystem {
-    dataset: libzfs::ZfsHandle,
+    pub(super) dataset: libzfs::ZfsHandle,
 }
 
 impl Filesystem {
@@ -16,10 +18,73 @@ impl Filesystem {
     }
 
     pub fn destroy(self) -> Result<()> {
+        if self.is_mounted() {
+            self.unmount(false)?;
+        }
         lzc::destroy_dataset(self.name())?;
         Ok(())
     }
 
+    /// Mount the filesystem at its `mountpoint`, as `zfs mount` does. Filesystems with
+    /// `canmount=off` or with `none` or `legacy` mountpoint are left alone. Unless
+    /// `overlay` is set (either the property or the option) the mountpoint must be empty.
+    pub fn mount(&self, options: MountOptions) -> Result<()> {
+        let flags = if options.overlay {
+            libzfs::MS_OVERLAY
+        } else {
+            0
+        };
+        let options = (!options.options.is_empty()).then(|| options.options.join(","));
+        self.dataset.mount(options.as_deref(), flags)?;
+        Ok(())
+    }
+
+    /// Unmount the filesystem (unsharing it first). With `force` busy filesystem
+    /// is unmounted as well.
+    pub fn unmount(&self, force: bool) -> Result<()> {
+        let flags = if force { libzfs::MS_FORCE } else { 0 };
+        self.dataset.unmount(flags)?;
+        Ok(())
+    }
+
+    pub fn is_mounted(&self) -> bool {
+        self.dataset.mounted().is_some()
+    }
+
+    /// Where the filesystem is currently mounted
+    pub fn mounted_at(&self) -> Option<String> {
+        self.dataset.mounted()
+    }
+
+    /// Mount `root` and all the filesystems under it, as `zfs mount -a` does for all the
+    /// pools. Only the filesystems with `canmount=on` are mounted, in the mountpoint order
+    /// so that a mountpoint is never shadowed by a later mount.
+    pub fn mount_all_under(root: impl AsRef<str>) -> Result<()> {
+        let mut filesystems = libzfs::zfs_list_from(root.as_ref())
+            .filesystems()
+            .recursive(true)
+            .get_collection()
+            .into_iter()
+            .map(|dataset| Self { dataset })
+            .collect::<Vec<_>>();
+        filesystems.push(Self::get(root)?);
+        // Path ordering is by components, the parent directory first
+        filesystems.sort_by_cached_key(|filesystem| {
+            filesystem
+                .dataset
+                .property_with_source(ZFS_PROP_MOUNTPOINT)
+                .map(|(mountpoint, _)| PathBuf::from(mountpoint))
+                .unwrap_or_default()
+        });
+
+        for filesystem in filesystems {
+            if filesystem.canmount() == property::CanMount::On && !filesystem.is_mounted() {
+                filesystem.mount(MountOptions::new())?;
+            }
+        }
+        Ok(())
+    }
+
     pub fn snapshot(&self, name: impl AsRef<str>) -> Result<()> {
         let snapshot = format!("{}@{}", self.name(), name.as_ref());
         lzc::create_snapshot(snapshot, None)?;
@@ -27,6 +92,7 @@ impl Filesystem {
     }
 
     pub fn destroy_recursive(&self) -> Result<()> {
+        self.dataset.unmount_all(0)?;
         let ns_datasets = libzfs::zfs_list_from(self.name())
             .filesystems()
             .volumes()
@@ -42,6 +108,29 @@ impl Filesystem {
         Ok(())
     }
 
+    /// User (`module:property`) properties with their values and sources
+    pub fn user_properties(&self) -> BTreeMap<String, (String, property::PropSource)> {
+        self.dataset.user_properties()
+    }
+
+    pub fn set_user_property(
+        &mut self,
+        name: impl AsRef<str>,
+        value: impl AsRef<str>,
+    ) -> Result<()> {
+        let mut props = Properties::new();
+        props.user_property(name, value)?;
+        self.dataset.set_properties(props)?;
+        Ok(())
+    }
+
+    /// Remove the local value of the user property, as `zfs inherit` does
+    pub fn inherit_user_property(&mut self, name: impl AsRef<str>) -> Result<()> {
+        property::validate_user_property(name.as_ref())?;
+        self.dataset.inherit(name.as_ref(), false)?;
+        Ok(())
+    }
+
     pub fn name(&self) -> String {
         self.dataset.name().to_string()
     }
@@ -166,6 +255,187 @@ impl Filesystem {
         self.dataset.numeric_property(ZFS_PROP_OBJSETID)
     }
 
+    #[inline]
+    pub fn quota(&self) -> property::ByteSize {
+        self.dataset.numeric_property(ZFS_PROP_QUOTA).into()
+    }
+
+    #[inline]
+    pub fn refquota(&self) -> property::ByteSize {
+        self.dataset.numeric_property(ZFS_PROP_REFQUOTA).into()
+    }
+
+    #[inline]
+    pub fn reservation(&self) -> property::ByteSize {
+        self.dataset.numeric_property(ZFS_PROP_RESERVATION).into()
+    }
+
+    #[inline]
+    pub fn refreservation(&self) -> property::ByteSize {
+        self.dataset
+            .numeric_property(ZFS_PROP_REFRESERVATION)
+            .into()
+    }
+
+    #[inline]
+    pub fn recordsize(&self) -> property::ByteSize {
+        self.dataset.numeric_property(ZFS_PROP_RECORDSIZE).into()
+    }
+
+    pub fn sharenfs(&self) -> String {
+        self.dataset
+            .property_with_source(ZFS_PROP_SHARENFS)
+            .map(|(value, _)| value)
+            .unwrap_or_default()
+    }
+
+    pub fn sharesmb(&self) -> String {
+        self.dataset
+            .property_with_source(ZFS_PROP_SHARESMB)
+            .map(|(value, _)| value)
+            .unwrap_or_default()
+    }
+
+    #[inline]
+    pub fn xattr(&self) -> Result<property::Xattr> {
+        let value = self.dataset.numeric_property(ZFS_PROP_XATTR).try_into()?;
+        Ok(value)
+    }
+
+    #[inline]
+    pub fn acltype(&self) -> Result<property::AclType> {
+        let value = self.dataset.numeric_property(ZFS_PROP_ACLTYPE).try_into()?;
+        Ok(value)
+    }
+
+    #[inline]
+    pub fn aclinherit(&self) -> Result<property::AclInherit> {
+        let value = self
+            .dataset
+            .numeric_property(ZFS_PROP_ACLINHERIT)
+            .try_into()?;
+        Ok(value)
+    }
+
+    #[inline]
+    pub fn dnodesize(&self) -> Result<property::DnodeSize> {
+        let value = self
+            .dataset
+            .numeric_property(ZFS_PROP_DNODESIZE)
+            .try_into()?;
+        Ok(value)
+    }
+
+    #[inline]
+    pub fn sync(&self) -> Result<property::SyncMode> {
+        let value = self.dataset.numeric_property(ZFS_PROP_SYNC).try_into()?;
+        Ok(value)
+    }
+
+    #[inline]
+    pub fn logbias(&self) -> Result<property::LogBias> {
+        let value = self.dataset.numeric_property(ZFS_PROP_LOGBIAS).try_into()?;
+        Ok(value)
+    }
+
+    #[inline]
+    pub fn primarycache(&self) -> Result<property::Cache> {
+        let value = self
+            .dataset
+            .numeric_property(ZFS_PROP_PRIMARYCACHE)
+            .try_into()?;
+        Ok(value)
+    }
+
+    #[inline]
+    pub fn secondarycache(&self) -> Result<property::Cache> {
+        let value = self
+            .dataset
+            .numeric_property(ZFS_PROP_SECONDARYCACHE)
+            .try_into()?;
+        Ok(value)
+    }
+
+    #[inline]
+    pub fn redundant_metadata(&self) -> Result<property::RedundantMetadata> {
+        let value = self
+            .dataset
+            .numeric_property(ZFS_PROP_REDUNDANT_METADATA)
+            .try_into()?;
+        Ok(value)
+    }
+
+    #[inline]
+    pub fn dedup(&self) -> Result<property::Dedup> {
+        let value = self.dataset.numeric_property(ZFS_PROP_DEDUP).try_into()?;
+        Ok(value)
+    }
+
+    #[inline]
+    pub fn copies(&self) -> u64 {
+        self.dataset.numeric_property(ZFS_PROP_COPIES)
+    }
+
+    #[inline]
+    pub fn snapdir(&self) -> Result<property::SnapDir> {
+        let value = self.dataset.numeric_property(ZFS_PROP_SNAPDIR).try_into()?;
+        Ok(value)
+    }
+
+    #[inline]
+    pub fn special_small_blocks(&self) -> u64 {
+        self.dataset.numeric_property(ZFS_PROP_SPECIAL_SMALL_BLOCKS)
+    }
+
+    #[inline]
+    pub fn usedbysnapshots(&self) -> u64 {
+        self.dataset.numeric_property(ZFS_PROP_USEDSNAP)
+    }
+
+    #[inline]
+    pub fn usedbydataset(&self) -> u64 {
+        self.dataset.numeric_property(ZFS_PROP_USEDDS)
+    }
+
+    #[inline]
+    pub fn usedbychildren(&self) -> u64 {
+        self.dataset.numeric_property(ZFS_PROP_USEDCHILD)
+    }
+
+    #[inline]
+    pub fn usedbyrefreservation(&self) -> u64 {
+        self.dataset.numeric_property(ZFS_PROP_USEDREFRESERV)
+    }
+
+    #[inline]
+    pub fn written(&self) -> u64 {
+        self.dataset.numeric_property(ZFS_PROP_WRITTEN)
+    }
+
+    #[inline]
+    pub fn filesystem_count(&self) -> u64 {
+        self.dataset.numeric_property(ZFS_PROP_FILESYSTEM_COUNT)
+    }
+
+    #[inline]
+    pub fn keystatus(&self) -> Result<property::KeyStatus> {
+        let value = self
+            .dataset
+            .numeric_property(ZFS_PROP_KEYSTATUS)
+            .try_into()?;
+        Ok(value)
+    }
+
+    /// Property value (in parsable form) along with its source: local, default,
+    /// inherited, received or temporary
+    pub fn get_property_detailed(
+        &self,
+        property: libzfs::zfs_prop_t,
+    ) -> Result<(String, PropSource)> {
+        let detailed = self.dataset.property_with_source(property)?;
+        Ok(detailed)
+    }
+
     pub fn get(name: impl AsRef<str>) -> Result<Self> {
         let cname = ffi::CString::new(name.as_ref())?;
         let dataset = libzfs::ZfsHandle::new(cname)?;
@@ -212,6 +482,36 @@ impl Serialize for Filesystem {
     }
 }
 
+/// Options of `Filesystem::mount()`
+///
+#[derive(Clone, Debug, Default)]
+pub struct MountOptions {
+    options: Vec<String>,
+    overlay: bool,
+}
+
+impl MountOptions {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Mount over non-empty directory, as `zfs mount -O` does
+    pub fn overlay(mut self, overlay: bool) -> Self {
+        self.overlay = overlay;
+        self
+    }
+
+    pub fn readonly(self) -> Self {
+        self.option("ro")
+    }
+
+    /// Temporary mount option, e.g. `noatime`, as `zfs mount -o` does
+    pub fn option(mut self, option: impl AsRef<str>) -> Self {
+        self.options.push(option.as_ref().to_string());
+        self
+    }
+}
+
 #[derive(Debug)]
 pub struct FilesystemBuilder {
     props: Properties,
@@ -223,13 +523,24 @@ impl FilesystemBuilder {
         Self { props }
     }
 
-    // TODO: should check mount options and mount the FS if needed
+    /// Create the filesystem, then mount and share it if `canmount=on`, as `zfs create` does.
+    /// Mounting is best effort: like `zfs create` ("filesystem successfully created, but
+    /// not mounted") the filesystem is returned even if it could not be mounted or shared.
+    /// Check `is_mounted()`, and call `mount()` to retry and get the reason. Only root
+    /// can mount, for the delegated (non root) users the mount is skipped.
     pub fn create(self, name: impl AsRef<str>) -> Result<Filesystem> {
         let cname = ffi::CString::new(name.as_ref())?;
+        self.props.validate(property::DatasetType::Filesystem)?;
         libzfs::create_filesystem(name, self.props)?;
         let dataset = libzfs::ZfsHandle::new(cname)?;
         let filesystem = Filesystem { dataset };
 
+        if filesystem.canmount() == property::CanMount::On && is_root() {
+            if filesystem.mount(MountOptions::new()).is_ok() {
+                let _shared = filesystem.dataset.share();
+            }
+        }
+
         Ok(filesystem)
     }
 
@@ -303,6 +614,106 @@ impl FilesystemBuilder {
         self
     }
 
+    pub fn quota(mut self, value: impl Into<property::ByteSize>) -> Self {
+        self.props.quota(value);
+        self
+    }
+
+    pub fn refquota(mut self, value: impl Into<property::ByteSize>) -> Self {
+        self.props.refquota(value);
+        self
+    }
+
+    pub fn reservation(mut self, value: impl Into<property::ByteSize>) -> Self {
+        self.props.reservation(value);
+        self
+    }
+
+    pub fn refreservation(mut self, value: impl Into<property::ByteSize>) -> Self {
+        self.props.refreservation(value);
+        self
+    }
+
+    pub fn recordsize(mut self, value: impl Into<property::ByteSize>) -> Self {
+        self.props.recordsize(value);
+        self
+    }
+
+    pub fn sharenfs(mut self, value: impl AsRef<str>) -> Self {
+        self.props.sharenfs(value);
+        self
+    }
+
+    pub fn sharesmb(mut self, value: impl AsRef<str>) -> Self {
+        self.props.sharesmb(value);
+        self
+    }
+
+    pub fn xattr(mut self, value: impl Into<property::Xattr>) -> Self {
+        self.props.xattr(value);
+        self
+    }
+
+    pub fn acltype(mut self, value: impl Into<property::AclType>) -> Self {
+        self.props.acltype(value);
+        self
+    }
+
+    pub fn aclinherit(mut self, value: impl Into<property::AclInherit>) -> Self {
+        self.props.aclinherit(value);
+        self
+    }
+
+    pub fn dnodesize(mut self, value: impl Into<property::DnodeSize>) -> Self {
+        self.props.dnodesize(value);
+        self
+    }
+
+    pub fn sync(mut self, value: impl Into<property::SyncMode>) -> Self {
+        self.props.sync(value);
+        self
+    }
+
+    pub fn logbias(mut self, value: impl Into<property::LogBias>) -> Self {
+        self.props.logbias(value);
+        self
+    }
+
+    pub fn primarycache(mut self, value: impl Into<property::Cache>) -> Self {
+        self.props.primarycache(value);
+        self
+    }
+
+    pub fn secondarycache(mut self, value: impl Into<property::Cache>) -> Self {
+        self.props.secondarycache(value);
+        self
+    }
+
+    pub fn redundant_metadata(mut self, value: impl Into<property::RedundantMetadata>) -> Self {
+        self.props.redundant_metadata(value);
+        self
+    }
+
+    pub fn dedup(mut self, value: impl Into<property::Dedup>) -> Self {
+        self.props.dedup(value);
+        self
+    }
+
+    pub fn copies(mut self, value: u64) -> Self {
+        self.props.copies(value);
+        self
+    }
+
+    pub fn snapdir(mut self, value: impl Into<property::SnapDir>) -> Self {
+        self.props.snapdir(value);
+        self
+    }
+
+    pub fn special_small_blocks(mut self, value: u64) -> Self {
+        self.props.special_small_blocks(value);
+        self
+    }
+
     pub fn property(mut self, property: &str, value: &str) -> Self {
         self.props.string_property(property, value);
         self
@@ -319,6 +730,7 @@ impl Default for FilesystemBuilder {
 pub struct FilesytemPropSetter<'a> {
     filesystem: &'a mut Filesystem,
     props: Properties,
+    inherit: Vec<String>,
 }
 
 impl<'a> FilesytemPropSetter<'a> {
@@ -326,6 +738,7 @@ impl<'a> FilesytemPropSetter<'a> {
         Self {
             filesystem,
             props: Properties::new(),
+            inherit: Vec::new(),
         }
     }
 
@@ -394,8 +807,122 @@ impl<'a> FilesytemPropSetter<'a> {
         self
     }
 
+    pub fn quota(mut self, value: impl Into<property::ByteSize>) -> Self {
+        self.props.quota(value);
+        self
+    }
+
+    pub fn refquota(mut self, value: impl Into<property::ByteSize>) -> Self {
+        self.props.refquota(value);
+        self
+    }
+
+    pub fn reservation(mut self, value: impl Into<property::ByteSize>) -> Self {
+        self.props.reservation(value);
+        self
+    }
+
+    pub fn refreservation(mut self, value: impl Into<property::ByteSize>) -> Self {
+        self.props.refreservation(value);
+        self
+    }
+
+    pub fn recordsize(mut self, value: impl Into<property::ByteSize>) -> Self {
+        self.props.recordsize(value);
+        self
+    }
+
+    pub fn sharenfs(mut self, value: impl AsRef<str>) -> Self {
+        self.props.sharenfs(value);
+        self
+    }
+
+    pub fn sharesmb(mut self, value: impl AsRef<str>) -> Self {
+        self.props.sharesmb(value);
+        self
+    }
+
+    pub fn xattr(mut self, value: impl Into<property::Xattr>) -> Self {
+        self.props.xattr(value);
+        self
+    }
+
+    pub fn acltype(mut self, value: impl Into<property::AclType>) -> Self {
+        self.props.acltype(value);
+        self
+    }
+
+    pub fn aclinherit(mut self, value: impl Into<property::AclInherit>) -> Self {
+        self.props.aclinherit(value);
+        self
+    }
+
+    pub fn dnodesize(mut self, value: impl Into<property::DnodeSize>) -> Self {
+        self.props.dnodesize(value);
+        self
+    }
+
+    pub fn sync(mut self, value: impl Into<property::SyncMode>) -> Self {
+        self.props.sync(value);
+        self
+    }
+
+    pub fn logbias(mut self, value: impl Into<property::LogBias>) -> Self {
+        self.props.logbias(value);
+        self
+    }
+
+    pub fn primarycache(mut self, value: impl Into<property::Cache>) -> Self {
+        self.props.primarycache(value);
+        self
+    }
+
+    pub fn secondarycache(mut self, value: impl Into<property::Cache>) -> Self {
+        self.props.secondarycache(value);
+        self
+    }
+
+    pub fn redundant_metadata(mut self, value: impl Into<property::RedundantMetadata>) -> Self {
+        self.props.redundant_metadata(value);
+        self
+    }
+
+    pub fn dedup(mut self, value: impl Into<property::Dedup>) -> Self {
+        self.props.dedup(value);
+        self
+    }
+
+    pub fn copies(mut self, value: u64) -> Self {
+        self.props.copies(value);
+        self
+    }
+
+    pub fn snapdir(mut self, value: impl Into<property::SnapDir>) -> Self {
+        self.props.snapdir(value);
+        self
+    }
+
+    pub fn special_small_blocks(mut self, value: u64) -> Self {
+        self.props.special_small_blocks(value);
+        self
+    }
+
+    /// Clear the local value, so that the property is inherited again, as `zfs inherit` does
+    pub fn inherit<'b>(mut self, property: impl Property<'b>) -> Self {
+        self.inherit.push(property.name().into_owned());
+        self
+    }
+
     pub fn commit(self) -> Result<()> {
         self.filesystem.dataset.set_properties(self.props)?;
+        for name in &self.inherit {
+            self.filesystem.dataset.inherit(name, false)?;
+        }
         Ok(())
     }
 }
+
+#[allow(unsafe_code)]
+fn is_root() -> bool {
+    unsafe { libc::geteuid() == 0 }
+}
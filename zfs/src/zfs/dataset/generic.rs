@@ -0,0 +1,159 @@
+use super::*;
+
+use libzfs::zfs_prop_t::*;
+
+const DELIMITERS: &[char] = &['/', '@', '#'];
+
+/// Dataset of any type, as opened by `Zfs::open()` or listed by `Zfs::list()`
+///
+#[derive(Debug)]
+pub enum Dataset {
+    Filesystem(Filesystem),
+    Volume(Volume),
+    Snapshot(Snapshot),
+    Bookmark(Bookmark),
+}
+
+impl Dataset {
+    pub fn open(name: impl AsRef<str>) -> Result<Self> {
+        let cname = ffi::CString::new(name.as_ref())?;
+        let dataset = libzfs::ZfsHandle::new(cname)?;
+        Self::try_from(dataset)
+    }
+
+    fn handle(&self) -> &libzfs::ZfsHandle {
+        match self {
+            Self::Filesystem(filesystem) => &filesystem.dataset,
+            Self::Volume(volume) => &volume.dataset,
+            Self::Snapshot(snapshot) => &snapshot.dataset,
+            Self::Bookmark(bookmark) => &bookmark.dataset,
+        }
+    }
+
+    pub fn name(&self) -> String {
+        self.handle().name().to_string()
+    }
+
+    /// Name of the pool the dataset belongs to
+    pub fn pool(&self) -> String {
+        let name = self.name();
+        match name.find(DELIMITERS) {
+            Some(end) => name[..end].to_string(),
+            None => name,
+        }
+    }
+
+    /// Parent filesystem, or the dataset of the snapshot or bookmark.
+    /// `None` for the pool root filesystem.
+    pub fn parent(&self) -> Option<String> {
+        let name = self.name();
+        name.rfind(DELIMITERS).map(|end| name[..end].to_string())
+    }
+
+    #[inline]
+    pub fn guid(&self) -> u64 {
+        self.handle().numeric_property(ZFS_PROP_GUID)
+    }
+
+    #[inline]
+    pub fn creation(&self) -> u64 {
+        self.handle().numeric_property(ZFS_PROP_CREATION)
+    }
+
+    /// Space used, always 0 for bookmarks
+    #[inline]
+    pub fn used(&self) -> u64 {
+        self.handle().numeric_property(ZFS_PROP_USED)
+    }
+
+    pub fn dataset_type(&self) -> property::DatasetType {
+        match self {
+            Self::Filesystem(_) => property::DatasetType::Filesystem,
+            Self::Volume(_) => property::DatasetType::Volume,
+            Self::Snapshot(_) => property::DatasetType::Snapshot,
+            Self::Bookmark(_) => property::DatasetType::Bookmark,
+        }
+    }
+
+    pub fn is_filesystem(&self) -> bool {
+        matches!(self, Self::Filesystem(_))
+    }
+
+    pub fn is_volume(&self) -> bool {
+        matches!(self, Self::Volume(_))
+    }
+
+    pub fn is_snapshot(&self) -> bool {
+        matches!(self, Self::Snapshot(_))
+    }
+
+    pub fn is_bookmark(&self) -> bool {
+        matches!(self, Self::Bookmark(_))
+    }
+}
+
+impl TryFrom<libzfs::ZfsHandle> for Dataset {
+    type Error = DatasetError;
+
+    fn try_from(dataset: libzfs::ZfsHandle) -> Result<Self> {
+        let r#type = dataset.r#type();
+        let dataset = if r#type.is_filesystem() {
+            Self::Filesystem(Filesystem { dataset })
+        } else if r#type.is_volume() {
+            Self::Volume(Volume { dataset })
+        } else if r#type.is_snapshot() {
+            Self::Snapshot(Snapshot { dataset })
+        } else if r#type.is_bookmark() {
+            Self::Bookmark(Bookmark { dataset })
+        } else {
+            return Err(DatasetError::unsupported_dataset_type(dataset.name()));
+        };
+        Ok(dataset)
+    }
+}
+
+/// Dataset listing, as `zfs list` does. Only the selected types are listed.
+///
+#[derive(Debug)]
+pub struct DatasetList {
+    collector: libzfs::DatasetCollectorBuilder,
+}
+
+impl DatasetList {
+    pub(crate) fn new(collector: libzfs::DatasetCollectorBuilder) -> Self {
+        Self { collector }
+    }
+
+    #[must_use]
+    pub fn filesystems(self) -> Self {
+        Self::new(self.collector.filesystems())
+    }
+
+    #[must_use]
+    pub fn volumes(self) -> Self {
+        Self::new(self.collector.volumes())
+    }
+
+    #[must_use]
+    pub fn snapshots(self) -> Self {
+        Self::new(self.collector.snapshots())
+    }
+
+    #[must_use]
+    pub fn bookmarks(self) -> Self {
+        Self::new(self.collector.bookmarks())
+    }
+
+    #[must_use]
+    pub fn recursive(self, yes: bool) -> Self {
+        Self::new(self.collector.recursive(yes))
+    }
+
+    pub fn get_collection(self) -> Vec<Dataset> {
+        self.collector
+            .get_collection()
+            .into_iter()
+            .filter_map(|dataset| Dataset::try_from(dataset).ok())
+            .collect()
+    }
+}
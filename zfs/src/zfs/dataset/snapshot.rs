@@ -1,10 +1,13 @@
+use std::collections::HashSet;
+use std::iter;
+
 use super::*;
 
 use libzfs::zfs_prop_t::*;
 
 #[derive(Debug)]
 pub struct Snapshot {
-    dataset: libzfs::ZfsHandle,
+    pub(super) dataset: libzfs::ZfsHandle,
 }
 
 impl Snapshot {
@@ -20,6 +23,29 @@ impl Snapshot {
         Ok(())
     }
 
+    /// User (`module:property`) properties with their values and sources
+    pub fn user_properties(&self) -> BTreeMap<String, (String, property::PropSource)> {
+        self.dataset.user_properties()
+    }
+
+    pub fn set_user_property(
+        &mut self,
+        name: impl AsRef<str>,
+        value: impl AsRef<str>,
+    ) -> Result<()> {
+        let mut props = Properties::new();
+        props.user_property(name, value)?;
+        self.dataset.set_properties(props)?;
+        Ok(())
+    }
+
+    /// Remove the local value of the user property, as `zfs inherit` does
+    pub fn inherit_user_property(&mut self, name: impl AsRef<str>) -> Result<()> {
+        property::validate_user_property(name.as_ref())?;
+        self.dataset.inherit(name.as_ref(), false)?;
+        Ok(())
+    }
+
     pub fn name(&self) -> String {
         self.dataset.name().to_string()
     }
@@ -30,13 +56,13 @@ impl Snapshot {
     }
 
     #[inline]
-    pub fn volsize(&self) -> u64 {
-        self.dataset.numeric_property(ZFS_PROP_VOLSIZE)
+    pub fn volsize(&self) -> property::ByteSize {
+        self.dataset.numeric_property(ZFS_PROP_VOLSIZE).into()
     }
 
     #[inline]
-    pub fn volblocksize(&self) -> u64 {
-        self.dataset.numeric_property(ZFS_PROP_VOLBLOCKSIZE)
+    pub fn volblocksize(&self) -> property::ByteSize {
+        self.dataset.numeric_property(ZFS_PROP_VOLBLOCKSIZE).into()
     }
 
     #[inline]
@@ -100,28 +126,73 @@ impl Snapshot {
     }
 }
 
+/// Takes the snapshot of one or more datasets, and optionally of their descendants,
+/// in a single `lzc_snapshot()` call, so all of them are taken atomically in the same txg.
+///
 #[derive(Debug)]
 pub struct SnapshotBuilder {
-    props: Result<nvpair::NvList>,
+    props: Properties,
+    datasets: Vec<String>,
     recursive: bool,
+    depth: Option<usize>,
+    exclude: Vec<String>,
+    err: Option<DatasetError>,
 }
 
 impl SnapshotBuilder {
     pub fn new() -> Self {
-        let props = Ok(nvpair::NvList::new());
-        let recursive = false;
-        Self { props, recursive }
+        Self {
+            props: Properties::new(),
+            datasets: Vec::new(),
+            recursive: false,
+            depth: None,
+            exclude: Vec::new(),
+            err: None,
+        }
     }
 
-    pub fn create(self, name: impl AsRef<str>) -> Result<Snapshot> {
-        let _props = self.props?;
+    /// Create `dataset@snapshot` along with the same snapshot of the datasets added
+    /// with `dataset()`, and returns all the created snapshots, `name` first.
+    /// The descendants are listed before the snapshot is taken: datasets created after
+    /// that are not included, and if any of the listed ones is gone no snapshot is taken.
+    /// Excluding the `name` dataset itself is an error.
+    pub fn create(self, name: impl AsRef<str>) -> Result<Vec<Snapshot>> {
+        if let Some(err) = self.err {
+            return Err(err);
+        }
+
         let name = name.as_ref();
-        if let Some((dataset, snapshot)) = name.split_once('@') {
-            snapshots(dataset, snapshot, self.recursive)?;
-        } else {
-            Err(DatasetError::invalid_snapshot_name(name))?;
+        let (dataset, snapshot) = name
+            .split_once('@')
+            .ok_or_else(|| DatasetError::invalid_snapshot_name(name))?;
+        // `name` comes first, and there is always something to snapshot
+        if self.is_excluded(dataset) {
+            return Err(DatasetError::excluded_dataset(dataset));
+        }
+
+        let mut datasets = Vec::new();
+        for root in iter::once(dataset).chain(self.datasets.iter().map(String::as_str)) {
+            datasets.push(root.to_string());
+            if self.recursive {
+                datasets.extend(self.descendants(root));
+            }
         }
-        Snapshot::get(name)
+        let mut seen = HashSet::new();
+        datasets.retain(|dataset| !self.is_excluded(dataset) && seen.insert(dataset.clone()));
+
+        let snapshots = datasets
+            .iter()
+            .map(|dataset| format!("{dataset}@{snapshot}"))
+            .collect::<Vec<_>>();
+        lzc::create_snapshots(&snapshots, self.props.into_inner())?;
+
+        snapshots.into_iter().map(Snapshot::get).collect()
+    }
+
+    /// Also snapshot this dataset (and its descendants if recursive), with the same name
+    pub fn dataset(mut self, dataset: impl AsRef<str>) -> Self {
+        self.datasets.push(dataset.as_ref().to_string());
+        self
     }
 
     pub fn recursive(self) -> Self {
@@ -130,6 +201,53 @@ impl SnapshotBuilder {
             ..self
         }
     }
+
+    /// Recursive, but at most `depth` levels below each dataset; 0 means the dataset only
+    pub fn depth(self, depth: usize) -> Self {
+        Self {
+            recursive: true,
+            depth: Some(depth),
+            ..self
+        }
+    }
+
+    /// Skip the datasets matching the pattern, where `*` matches any characters including `/`,
+    /// e.g. `pool/tmp` or `pool/home/*/cache`. Descendants of the excluded dataset are
+    /// only skipped if they match too, e.g. `pool/tmp*`.
+    pub fn exclude(mut self, pattern: impl AsRef<str>) -> Self {
+        self.exclude.push(pattern.as_ref().to_string());
+        self
+    }
+
+    /// User (`module:property`) property, the only kind a snapshot can be created with
+    pub fn user_property(mut self, name: impl AsRef<str>, value: impl AsRef<str>) -> Self {
+        if let Err(err) = self.props.user_property(name, value) {
+            self.err.get_or_insert(err.into());
+        }
+        self
+    }
+
+    fn descendants(&self, root: &str) -> Vec<String> {
+        libzfs::zfs_list_from(root)
+            .filesystems()
+            .volumes()
+            .recursive(true)
+            .get_collection()
+            .into_iter()
+            .map(|dataset| dataset.name().to_string())
+            .filter(|name| {
+                self.depth.map_or(true, |depth| {
+                    name[root.len()..].matches('/').count() <= depth
+                })
+            })
+            .collect()
+    }
+
+    fn is_excluded(&self, dataset: &str) -> bool {
+        self.exclude
+            .iter()
+            .any(|pattern| matches_pattern(pattern, dataset))
+    }
 }
 
 impl Default for SnapshotBuilder {
@@ -138,16 +256,33 @@ impl Default for SnapshotBuilder {
     }
 }
 
-fn snapshots(dataset: impl AsRef<str>, snapshot: impl AsRef<str>, recursive: bool) -> Result<()> {
-    let snapshot = snapshot.as_ref();
-    let snapshots = libzfs::zfs_list_from(dataset)
-        .filesystems()
-        .volumes()
-        .recursive(recursive)
-        .get_collection()
-        .into_iter()
-        .map(|dataset| format!("{}@{}", dataset.name(), snapshot));
-
-    lzc::create_snapshots(snapshots, None)?;
-    Ok(())
+fn matches_pattern(pattern: &str, name: &str) -> bool {
+    match pattern.split_once('*') {
+        None => pattern == name,
+        Some((prefix, rest)) => {
+            if let Some(name) = name.strip_prefix(prefix) {
+                name.char_indices()
+                    .map(|(index, _)| index)
+                    .chain(iter::once(name.len()))
+                    .any(|index| matches_pattern(rest, &name[index..]))
+            } else {
+                false
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn exclude_patterns() {
+        assert!(matches_pattern("pool/tmp", "pool/tmp"));
+        assert!(!matches_pattern("pool/tmp", "pool/tmp/a"));
+        assert!(matches_pattern("pool/tmp*", "pool/tmp/a/b"));
+        assert!(matches_pattern("pool/home/*/cache", "pool/home/joe/cache"));
+        assert!(!matches_pattern("pool/home/*/cache", "pool/home/joe/data"));
+        assert!(matches_pattern("*", "pool"));
+    }
 }
@@ -11,7 +11,7 @@ use property::*;
 
 #[derive(Debug)]
 pub struct Volume {
-    dataset: libzfs::ZfsHandle,
+    pub(super) dataset: libzfs::ZfsHandle,
 }
 
 impl Volume {
@@ -30,6 +30,29 @@ impl Volume {
         Ok(())
     }
 
+    /// User (`module:property`) properties with their values and sources
+    pub fn user_properties(&self) -> BTreeMap<String, (String, property::PropSource)> {
+        self.dataset.user_properties()
+    }
+
+    pub fn set_user_property(
+        &mut self,
+        name: impl AsRef<str>,
+        value: impl AsRef<str>,
+    ) -> Result<()> {
+        let mut props = Properties::new();
+        props.user_property(name, value)?;
+        self.dataset.set_properties(props)?;
+        Ok(())
+    }
+
+    /// Remove the local value of the user property, as `zfs inherit` does
+    pub fn inherit_user_property(&mut self, name: impl AsRef<str>) -> Result<()> {
+        property::validate_user_property(name.as_ref())?;
+        self.dataset.inherit(name.as_ref(), false)?;
+        Ok(())
+    }
+
     pub fn name(&self) -> String {
         self.dataset.name().to_string()
     }
@@ -47,13 +70,13 @@ impl Volume {
     }
 
     #[inline]
-    pub fn volsize(&self) -> u64 {
-        self.dataset.numeric_property(ZFS_PROP_VOLSIZE)
+    pub fn volsize(&self) -> property::ByteSize {
+        self.dataset.numeric_property(ZFS_PROP_VOLSIZE).into()
     }
 
     #[inline]
-    pub fn volblocksize(&self) -> u64 {
-        self.dataset.numeric_property(ZFS_PROP_VOLBLOCKSIZE)
+    pub fn volblocksize(&self) -> property::ByteSize {
+        self.dataset.numeric_property(ZFS_PROP_VOLBLOCKSIZE).into()
     }
 
     #[inline]
@@ -115,6 +138,102 @@ impl Volume {
     pub fn objsetid(&self) -> u64 {
         self.dataset.numeric_property(ZFS_PROP_OBJSETID)
     }
+
+    #[inline]
+    pub fn reservation(&self) -> property::ByteSize {
+        self.dataset.numeric_property(ZFS_PROP_RESERVATION).into()
+    }
+
+    #[inline]
+    pub fn refreservation(&self) -> property::ByteSize {
+        self.dataset
+            .numeric_property(ZFS_PROP_REFRESERVATION)
+            .into()
+    }
+
+    #[inline]
+    pub fn sync(&self) -> Result<property::SyncMode> {
+        let value = self.dataset.numeric_property(ZFS_PROP_SYNC).try_into()?;
+        Ok(value)
+    }
+
+    #[inline]
+    pub fn logbias(&self) -> Result<property::LogBias> {
+        let value = self.dataset.numeric_property(ZFS_PROP_LOGBIAS).try_into()?;
+        Ok(value)
+    }
+
+    #[inline]
+    pub fn primarycache(&self) -> Result<property::Cache> {
+        let value = self
+            .dataset
+            .numeric_property(ZFS_PROP_PRIMARYCACHE)
+            .try_into()?;
+        Ok(value)
+    }
+
+    #[inline]
+    pub fn secondarycache(&self) -> Result<property::Cache> {
+        let value = self
+            .dataset
+            .numeric_property(ZFS_PROP_SECONDARYCACHE)
+            .try_into()?;
+        Ok(value)
+    }
+
+    #[inline]
+    pub fn redundant_metadata(&self) -> Result<property::RedundantMetadata> {
+        let value = self
+            .dataset
+            .numeric_property(ZFS_PROP_REDUNDANT_METADATA)
+            .try_into()?;
+        Ok(value)
+    }
+
+    #[inline]
+    pub fn dedup(&self) -> Result<property::Dedup> {
+        let value = self.dataset.numeric_property(ZFS_PROP_DEDUP).try_into()?;
+        Ok(value)
+    }
+
+    #[inline]
+    pub fn copies(&self) -> u64 {
+        self.dataset.numeric_property(ZFS_PROP_COPIES)
+    }
+
+    #[inline]
+    pub fn usedbysnapshots(&self) -> u64 {
+        self.dataset.numeric_property(ZFS_PROP_USEDSNAP)
+    }
+
+    #[inline]
+    pub fn usedbydataset(&self) -> u64 {
+        self.dataset.numeric_property(ZFS_PROP_USEDDS)
+    }
+
+    #[inline]
+    pub fn usedbychildren(&self) -> u64 {
+        self.dataset.numeric_property(ZFS_PROP_USEDCHILD)
+    }
+
+    #[inline]
+    pub fn usedbyrefreservation(&self) -> u64 {
+        self.dataset.numeric_property(ZFS_PROP_USEDREFRESERV)
+    }
+
+    #[inline]
+    pub fn written(&self) -> u64 {
+        self.dataset.numeric_property(ZFS_PROP_WRITTEN)
+    }
+
+    #[inline]
+    pub fn keystatus(&self) -> Result<property::KeyStatus> {
+        let value = self
+            .dataset
+            .numeric_property(ZFS_PROP_KEYSTATUS)
+            .try_into()?;
+        Ok(value)
+    }
 }
 
 impl Serialize for Volume {
@@ -147,7 +266,7 @@ impl Serialize for Volume {
 #[derive(Debug)]
 pub struct VolumeBuilder {
     props: Properties,
-    volblocksize: u64,
+    volblocksize: property::ByteSize,
     err: Option<DatasetError>,
 }
 
@@ -168,7 +287,11 @@ impl VolumeBuilder {
     //       3. add noreserve functionality
     //       4. add parents creation if needed
     //       5. add zfs_mount_and_share functionality
-    pub fn create(mut self, name: impl AsRef<str>, size: u64) -> Result<Volume> {
+    pub fn create(
+        mut self,
+        name: impl AsRef<str>,
+        size: impl Into<property::ByteSize>,
+    ) -> Result<Volume> {
         #[inline]
         fn _is_power_of_two(num: u64) -> bool {
             (num != 0) && ((num & (num - 1)) == 0)
@@ -183,6 +306,7 @@ impl VolumeBuilder {
 
         self.props.volsize(size);
         self.props.volblocksize(self.volblocksize);
+        self.props.validate(property::DatasetType::Volume)?;
 
         lzc::create_volume(name, self.props.into_inner())?;
 
@@ -202,14 +326,14 @@ impl VolumeBuilder {
         self
     }
 
-    pub fn blocksize(mut self, v: u64) -> Self {
-        self.volblocksize = v;
+    pub fn blocksize(mut self, v: impl Into<property::ByteSize>) -> Self {
+        self.volblocksize = v.into();
         self
     }
 
     // TODO: implement calculation algorithm
-    fn calculate_default_volblocksize() -> u64 {
-        8192
+    fn calculate_default_volblocksize() -> property::ByteSize {
+        property::ByteSize::new(8192)
     }
 
     pub fn volmode(mut self, value: impl Into<property::VolMode>) -> Self {
@@ -217,6 +341,51 @@ impl VolumeBuilder {
         self
     }
 
+    pub fn reservation(mut self, value: impl Into<property::ByteSize>) -> Self {
+        self.props.reservation(value);
+        self
+    }
+
+    pub fn refreservation(mut self, value: impl Into<property::ByteSize>) -> Self {
+        self.props.refreservation(value);
+        self
+    }
+
+    pub fn sync(mut self, value: impl Into<property::SyncMode>) -> Self {
+        self.props.sync(value);
+        self
+    }
+
+    pub fn logbias(mut self, value: impl Into<property::LogBias>) -> Self {
+        self.props.logbias(value);
+        self
+    }
+
+    pub fn primarycache(mut self, value: impl Into<property::Cache>) -> Self {
+        self.props.primarycache(value);
+        self
+    }
+
+    pub fn secondarycache(mut self, value: impl Into<property::Cache>) -> Self {
+        self.props.secondarycache(value);
+        self
+    }
+
+    pub fn redundant_metadata(mut self, value: impl Into<property::RedundantMetadata>) -> Self {
+        self.props.redundant_metadata(value);
+        self
+    }
+
+    pub fn dedup(mut self, value: impl Into<property::Dedup>) -> Self {
+        self.props.dedup(value);
+        self
+    }
+
+    pub fn copies(mut self, value: u64) -> Self {
+        self.props.copies(value);
+        self
+    }
+
     pub fn property(mut self, property: &str, value: &str) -> Self {
         self.props.string_property(property, value);
         self
@@ -253,7 +422,7 @@ impl<'a> VolumePropSetter<'a> {
         self
     }
 
-    pub fn blocksize(mut self, value: u64) -> Self {
+    pub fn blocksize(mut self, value: impl Into<property::ByteSize>) -> Self {
         self.props.volblocksize(value);
         self
     }
@@ -263,6 +432,51 @@ impl<'a> VolumePropSetter<'a> {
         self
     }
 
+    pub fn reservation(mut self, value: impl Into<property::ByteSize>) -> Self {
+        self.props.reservation(value);
+        self
+    }
+
+    pub fn refreservation(mut self, value: impl Into<property::ByteSize>) -> Self {
+        self.props.refreservation(value);
+        self
+    }
+
+    pub fn sync(mut self, value: impl Into<property::SyncMode>) -> Self {
+        self.props.sync(value);
+        self
+    }
+
+    pub fn logbias(mut self, value: impl Into<property::LogBias>) -> Self {
+        self.props.logbias(value);
+        self
+    }
+
+    pub fn primarycache(mut self, value: impl Into<property::Cache>) -> Self {
+        self.props.primarycache(value);
+        self
+    }
+
+    pub fn secondarycache(mut self, value: impl Into<property::Cache>) -> Self {
+        self.props.secondarycache(value);
+        self
+    }
+
+    pub fn redundant_metadata(mut self, value: impl Into<property::RedundantMetadata>) -> Self {
+        self.props.redundant_metadata(value);
+        self
+    }
+
+    pub fn dedup(mut self, value: impl Into<property::Dedup>) -> Self {
+        self.props.dedup(value);
+        self
+    }
+
+    pub fn copies(mut self, value: u64) -> Self {
+        self.props.copies(value);
+        self
+    }
+
     pub fn commit(self) -> Result<()> {
         self.volume.dataset.set_properties(self.props)?;
         Ok(())
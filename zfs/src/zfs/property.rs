@@ -7,23 +7,57 @@ use libzfs::zfs_prop_t::*;
 use super::*;
 
 pub use error::InvalidProperty;
+pub use libzfs::zfs_prop_t;
+pub use libzfs::PropSource;
 
+pub use aclinherit::AclInherit;
+pub use acltype::AclType;
+pub use bytesize::ByteSize;
+pub use cache::Cache;
 pub use canmount::CanMount;
 pub use checksum::CheckSum;
 pub use compression::Compression;
+pub use compression::ZstdFastLevel;
+pub use compression::ZstdLevel;
+pub use dedup::Dedup;
+pub use dnodesize::DnodeSize;
+pub use keystatus::KeyStatus;
+pub use logbias::LogBias;
 pub use mountpoint::MountPoint;
 pub use onfoff::OnOff;
+pub use redundantmetadata::RedundantMetadata;
+pub use registry::registry;
+pub use registry::DatasetType;
+pub use registry::PropInfo;
+pub use registry::PropType;
+pub use registry::Registry;
+pub use snapdir::SnapDir;
+pub use sync::SyncMode;
 pub use volmode::VolMode;
+pub use xattr::Xattr;
 pub use yesno::YesNo;
 
 mod error;
 
+mod aclinherit;
+mod acltype;
+mod bytesize;
+mod cache;
 mod canmount;
 mod checksum;
 mod compression;
+mod dedup;
+mod dnodesize;
+mod keystatus;
+mod logbias;
 mod mountpoint;
 mod onfoff;
+mod redundantmetadata;
+mod registry;
+mod snapdir;
+mod sync;
 mod volmode;
+mod xattr;
 mod yesno;
 
 type PropName = Lazy<Cow<'static, str>>;
@@ -69,6 +103,47 @@ pub static RELATIME: PropName = Lazy::new(|| prop_name(ZFS_PROP_RELATIME));
 pub static VSCAN: PropName = Lazy::new(|| prop_name(ZFS_PROP_VSCAN));
 pub static OVERLAY: PropName = Lazy::new(|| prop_name(ZFS_PROP_OVERLAY));
 
+pub static REFQUOTA: PropName = Lazy::new(|| prop_name(ZFS_PROP_REFQUOTA));
+pub static SHARESMB: PropName = Lazy::new(|| prop_name(ZFS_PROP_SHARESMB));
+pub static XATTR: PropName = Lazy::new(|| prop_name(ZFS_PROP_XATTR));
+pub static ACLTYPE: PropName = Lazy::new(|| prop_name(ZFS_PROP_ACLTYPE));
+pub static ACLINHERIT: PropName = Lazy::new(|| prop_name(ZFS_PROP_ACLINHERIT));
+pub static DNODESIZE: PropName = Lazy::new(|| prop_name(ZFS_PROP_DNODESIZE));
+pub static SYNC: PropName = Lazy::new(|| prop_name(ZFS_PROP_SYNC));
+pub static LOGBIAS: PropName = Lazy::new(|| prop_name(ZFS_PROP_LOGBIAS));
+pub static PRIMARYCACHE: PropName = Lazy::new(|| prop_name(ZFS_PROP_PRIMARYCACHE));
+pub static SECONDARYCACHE: PropName = Lazy::new(|| prop_name(ZFS_PROP_SECONDARYCACHE));
+pub static REDUNDANT_METADATA: PropName = Lazy::new(|| prop_name(ZFS_PROP_REDUNDANT_METADATA));
+pub static DEDUP: PropName = Lazy::new(|| prop_name(ZFS_PROP_DEDUP));
+pub static COPIES: PropName = Lazy::new(|| prop_name(ZFS_PROP_COPIES));
+pub static SNAPDIR: PropName = Lazy::new(|| prop_name(ZFS_PROP_SNAPDIR));
+pub static SPECIAL_SMALL_BLOCKS: PropName = Lazy::new(|| prop_name(ZFS_PROP_SPECIAL_SMALL_BLOCKS));
+pub static USEDBYSNAPSHOTS: PropName = Lazy::new(|| prop_name(ZFS_PROP_USEDSNAP));
+pub static USEDBYDATASET: PropName = Lazy::new(|| prop_name(ZFS_PROP_USEDDS));
+pub static USEDBYCHILDREN: PropName = Lazy::new(|| prop_name(ZFS_PROP_USEDCHILD));
+pub static USEDBYREFRESERVATION: PropName = Lazy::new(|| prop_name(ZFS_PROP_USEDREFRESERV));
+pub static WRITTEN: PropName = Lazy::new(|| prop_name(ZFS_PROP_WRITTEN));
+pub static FILESYSTEM_COUNT: PropName = Lazy::new(|| prop_name(ZFS_PROP_FILESYSTEM_COUNT));
+pub static KEYSTATUS: PropName = Lazy::new(|| prop_name(ZFS_PROP_KEYSTATUS));
+
+/// Longest property name accepted by the kernel (`ZAP_MAXNAMELEN`, including the NUL)
+const USER_PROPERTY_MAXNAMELEN: usize = 256;
+
+/// Check the user property name the way `zfs set` does: it must contain a colon
+/// (`module:property`) and consist only of lowercase letters, digits and `:`, `_`, `.`, `-`.
+pub fn validate_user_property(name: &str) -> Result<(), InvalidProperty> {
+    let valid = name.contains(':')
+        && name.len() < USER_PROPERTY_MAXNAMELEN
+        && name
+            .chars()
+            .all(|c| matches!(c, 'a'..='z' | '0'..='9' | ':' | '_' | '.' | '-'));
+    if valid {
+        Ok(())
+    } else {
+        Err(InvalidProperty::invalid_user_property(name))
+    }
+}
+
 #[inline]
 pub fn prop_name(prop: libzfs::zfs_prop_t) -> Cow<'static, str> {
     libzfs::zfs_prop_to_name(prop)
@@ -138,12 +213,12 @@ impl Properties {
         self.set_string(ZFS_PROP_COMPRESSION, compression.into());
     }
 
-    pub fn volsize(&mut self, size: u64) {
-        self.set_numeric(ZFS_PROP_VOLSIZE, size);
+    pub fn volsize(&mut self, size: impl Into<ByteSize>) {
+        self.set_numeric(ZFS_PROP_VOLSIZE, size.into());
     }
 
-    pub fn volblocksize(&mut self, blocksize: u64) {
-        self.set_numeric(ZFS_PROP_VOLBLOCKSIZE, blocksize);
+    pub fn volblocksize(&mut self, blocksize: impl Into<ByteSize>) {
+        self.set_numeric(ZFS_PROP_VOLBLOCKSIZE, blocksize.into());
     }
 
     pub fn mountpoint(&mut self, mountpoint: impl AsRef<str>) {
@@ -153,6 +228,86 @@ impl Properties {
         self.set_numeric(ZFS_PROP_VOLMODE, volmode.into());
     }
 
+    pub fn quota(&mut self, quota: impl Into<ByteSize>) {
+        self.set_numeric(ZFS_PROP_QUOTA, quota.into());
+    }
+
+    pub fn refquota(&mut self, refquota: impl Into<ByteSize>) {
+        self.set_numeric(ZFS_PROP_REFQUOTA, refquota.into());
+    }
+
+    pub fn reservation(&mut self, reservation: impl Into<ByteSize>) {
+        self.set_numeric(ZFS_PROP_RESERVATION, reservation.into());
+    }
+
+    pub fn refreservation(&mut self, refreservation: impl Into<ByteSize>) {
+        self.set_numeric(ZFS_PROP_REFRESERVATION, refreservation.into());
+    }
+
+    pub fn recordsize(&mut self, recordsize: impl Into<ByteSize>) {
+        self.set_numeric(ZFS_PROP_RECORDSIZE, recordsize.into());
+    }
+
+    pub fn sharenfs(&mut self, sharenfs: impl AsRef<str>) {
+        self.set_string(ZFS_PROP_SHARENFS, sharenfs.as_ref());
+    }
+
+    pub fn sharesmb(&mut self, sharesmb: impl AsRef<str>) {
+        self.set_string(ZFS_PROP_SHARESMB, sharesmb.as_ref());
+    }
+
+    pub fn xattr(&mut self, xattr: impl Into<Xattr>) {
+        self.set_string(ZFS_PROP_XATTR, xattr.into());
+    }
+
+    pub fn acltype(&mut self, acltype: impl Into<AclType>) {
+        self.set_string(ZFS_PROP_ACLTYPE, acltype.into());
+    }
+
+    pub fn aclinherit(&mut self, aclinherit: impl Into<AclInherit>) {
+        self.set_string(ZFS_PROP_ACLINHERIT, aclinherit.into());
+    }
+
+    pub fn dnodesize(&mut self, dnodesize: impl Into<DnodeSize>) {
+        self.set_string(ZFS_PROP_DNODESIZE, dnodesize.into());
+    }
+
+    pub fn sync(&mut self, sync: impl Into<SyncMode>) {
+        self.set_string(ZFS_PROP_SYNC, sync.into());
+    }
+
+    pub fn logbias(&mut self, logbias: impl Into<LogBias>) {
+        self.set_string(ZFS_PROP_LOGBIAS, logbias.into());
+    }
+
+    pub fn primarycache(&mut self, primarycache: impl Into<Cache>) {
+        self.set_string(ZFS_PROP_PRIMARYCACHE, primarycache.into());
+    }
+
+    pub fn secondarycache(&mut self, secondarycache: impl Into<Cache>) {
+        self.set_string(ZFS_PROP_SECONDARYCACHE, secondarycache.into());
+    }
+
+    pub fn redundant_metadata(&mut self, redundant_metadata: impl Into<RedundantMetadata>) {
+        self.set_string(ZFS_PROP_REDUNDANT_METADATA, redundant_metadata.into());
+    }
+
+    pub fn dedup(&mut self, dedup: impl Into<Dedup>) {
+        self.set_string(ZFS_PROP_DEDUP, dedup.into());
+    }
+
+    pub fn copies(&mut self, copies: u64) {
+        self.set_numeric(ZFS_PROP_COPIES, copies);
+    }
+
+    pub fn snapdir(&mut self, snapdir: impl Into<SnapDir>) {
+        self.set_string(ZFS_PROP_SNAPDIR, snapdir.into());
+    }
+
+    pub fn special_small_blocks(&mut self, special_small_blocks: u64) {
+        self.set_numeric(ZFS_PROP_SPECIAL_SMALL_BLOCKS, special_small_blocks);
+    }
+
     pub fn string_property<'a>(
         &mut self,
         property: impl Property<'a>,
@@ -161,6 +316,47 @@ impl Properties {
         self.set_string(property, value)
     }
 
+    /// User (`module:property`) property, the name is validated
+    pub fn user_property(
+        &mut self,
+        name: impl AsRef<str>,
+        value: impl AsRef<str>,
+    ) -> Result<&mut Self, InvalidProperty> {
+        validate_user_property(name.as_ref())?;
+        Ok(self.set_string(name.as_ref(), value))
+    }
+
+    /// Check the properties before they are submitted: first one by one against
+    /// the registry for the precise error, then all together with `zfs_valid_proplist()`
+    /// the way `zfs create` does.
+    pub fn validate(&self, r#type: DatasetType) -> Result<(), InvalidProperty> {
+        for pair in self.props.iter() {
+            let name = pair.name();
+            if name.contains(':') {
+                validate_user_property(&name)?;
+                continue;
+            }
+
+            let info = registry()
+                .get(&name)
+                .ok_or_else(|| InvalidProperty::no_such_property(&name))?;
+            match pair.value() {
+                nvpair::Value::U64(value) => info.validate_numeric(value, r#type)?,
+                nvpair::Value::String(value) => info.validate(&value, r#type)?,
+                value => {
+                    return Err(InvalidProperty::invalid_property_value(
+                        &name,
+                        format!("{value:?}"),
+                    ))
+                }
+            }
+        }
+
+        libzfs::zfs_valid_proplist(r#type.into(), &self.props, None)
+            .map(|_| ())
+            .map_err(|err| InvalidProperty::rejected(err.description()))
+    }
+
     pub(crate) fn into_inner(self) -> nvpair::NvList {
         self.props
     }
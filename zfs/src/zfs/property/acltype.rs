@@ -0,0 +1,88 @@
+#![allow(clippy::use_self)]
+
+use std::fmt;
+use std::str;
+
+use serde::{Deserialize, Serialize};
+
+use super::*;
+
+// acltype=off|posix|nfsv4
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum AclType {
+    #[default]
+    Off,
+    Posix,
+    Nfsv4,
+}
+
+impl AclType {
+    pub fn as_str(&self) -> &str {
+        match self {
+            Self::Off => "off",
+            Self::Posix => "posix",
+            Self::Nfsv4 => "nfsv4",
+        }
+    }
+}
+
+impl AsRef<str> for AclType {
+    fn as_ref(&self) -> &str {
+        self.as_str()
+    }
+}
+
+impl fmt::Display for AclType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.as_str().fmt(f)
+    }
+}
+
+impl str::FromStr for AclType {
+    type Err = error::InvalidProperty;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "off" | "noacl" => Ok(Self::Off),
+            "posix" | "posixacl" => Ok(Self::Posix),
+            "nfsv4" => Ok(Self::Nfsv4),
+            other => Err(error::InvalidProperty::invalid_value(other)),
+        }
+    }
+}
+
+macro_rules! numeric {
+    ($numeric:ty) => {
+        impl TryFrom<$numeric> for AclType {
+            type Error = error::InvalidProperty;
+
+            fn try_from(value: $numeric) -> Result<Self, Self::Error> {
+                match value {
+                    0 => Ok(Self::Off),
+                    1 => Ok(Self::Posix),
+                    2 => Ok(Self::Nfsv4),
+                    _ => Err(error::InvalidProperty::invalid_value(value)),
+                }
+            }
+        }
+
+        impl From<AclType> for $numeric {
+            fn from(value: AclType) -> Self {
+                match value {
+                    AclType::Off => 0,
+                    AclType::Posix => 1,
+                    AclType::Nfsv4 => 2,
+                }
+            }
+        }
+    };
+}
+
+numeric!(i8);
+numeric!(i16);
+numeric!(i32);
+numeric!(i64);
+numeric!(u8);
+numeric!(u16);
+numeric!(u32);
+numeric!(u64);
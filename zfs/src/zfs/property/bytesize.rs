@@ -0,0 +1,206 @@
+use std::fmt;
+use std::str;
+
+use serde::de::{self, Deserializer, Visitor};
+use serde::{Deserialize, Serialize, Serializer};
+
+use super::*;
+
+const UNITS: &[u8] = b"BKMGTPE";
+
+/// Size in bytes, e.g. `volsize` or `quota`. Parses the sizes the way `zfs set` does
+/// (`512`, `4K`, `1.5G`, `10TiB`, `none`) and displays them the way `zfs list` does.
+///
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct ByteSize(u64);
+
+impl ByteSize {
+    pub const fn new(bytes: u64) -> Self {
+        Self(bytes)
+    }
+
+    pub const fn as_u64(&self) -> u64 {
+        self.0
+    }
+
+    /// Human readable form, as `zfs_nicenum()` formats it: at most 5 characters
+    /// with 1024 based suffix, e.g. `512`, `4K`, `1.50G`, `10.5T`
+    fn nicenum(&self) -> String {
+        let bytes = self.0;
+        let mut index = 0;
+        let mut n = bytes;
+        while n >= 1024 && index < UNITS.len() - 1 {
+            n /= 1024;
+            index += 1;
+        }
+
+        if index == 0 {
+            return bytes.to_string();
+        }
+
+        let unit = char::from(UNITS[index]);
+        let divisor = 1_u64 << (10 * index);
+        if bytes % divisor == 0 {
+            return format!("{n}{unit}");
+        }
+
+        let value = bytes as f64 / divisor as f64;
+        [2_usize, 1, 0]
+            .into_iter()
+            .map(|precision| format!("{value:.precision$}{unit}"))
+            .find(|text| text.len() <= 5)
+            .unwrap_or_else(|| format!("{value:.0}{unit}"))
+    }
+}
+
+impl fmt::Display for ByteSize {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.nicenum().fmt(f)
+    }
+}
+
+impl str::FromStr for ByteSize {
+    type Err = error::InvalidProperty;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || error::InvalidProperty::invalid_value(s);
+        let text = s.trim();
+        if text.eq_ignore_ascii_case("none") {
+            return Ok(Self(0));
+        }
+
+        let split = text
+            .find(|c: char| !(c.is_ascii_digit() || c == '.'))
+            .unwrap_or(text.len());
+        let (number, suffix) = text.split_at(split);
+        if number.is_empty() {
+            return Err(invalid());
+        }
+
+        let suffix = suffix.to_ascii_uppercase();
+        let shift = match suffix.as_str() {
+            "" | "B" => 0,
+            suffix => {
+                let (unit, rest) = suffix.split_at(1);
+                if !matches!(rest, "" | "B" | "IB") {
+                    return Err(invalid());
+                }
+                UNITS
+                    .iter()
+                    .position(|u| unit.as_bytes()[0] == *u)
+                    .filter(|&index| index > 0)
+                    .ok_or_else(invalid)?
+                    * 10
+            }
+        };
+
+        let bytes = if number.contains('.') {
+            let value = number.parse::<f64>().map_err(|_| invalid())?;
+            let bytes = value * (1_u64 << shift) as f64;
+            if bytes >= u64::MAX as f64 {
+                return Err(invalid());
+            }
+            bytes.round() as u64
+        } else {
+            number
+                .parse::<u64>()
+                .ok()
+                .and_then(|value| value.checked_mul(1 << shift))
+                .ok_or_else(invalid)?
+        };
+
+        Ok(Self(bytes))
+    }
+}
+
+impl From<u64> for ByteSize {
+    fn from(bytes: u64) -> Self {
+        Self(bytes)
+    }
+}
+
+impl From<ByteSize> for u64 {
+    fn from(size: ByteSize) -> Self {
+        size.0
+    }
+}
+
+impl Serialize for ByteSize {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_u64(self.0)
+    }
+}
+
+impl<'de> Deserialize<'de> for ByteSize {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_any(ByteSizeVisitor)
+    }
+}
+
+struct ByteSizeVisitor;
+
+impl<'de> Visitor<'de> for ByteSizeVisitor {
+    type Value = ByteSize;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        formatter.write_str("size in bytes or size string like 1.5G")
+    }
+
+    fn visit_u64<E>(self, value: u64) -> Result<Self::Value, E>
+    where
+        E: de::Error,
+    {
+        Ok(ByteSize(value))
+    }
+
+    fn visit_i64<E>(self, value: i64) -> Result<Self::Value, E>
+    where
+        E: de::Error,
+    {
+        u64::try_from(value)
+            .map(ByteSize)
+            .map_err(|_| E::invalid_value(de::Unexpected::Signed(value), &self))
+    }
+
+    fn visit_str<E>(self, value: &str) -> Result<Self::Value, E>
+    where
+        E: de::Error,
+    {
+        value.parse().map_err(E::custom)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse() {
+        assert_eq!("512".parse::<ByteSize>().unwrap().as_u64(), 512);
+        assert_eq!("4K".parse::<ByteSize>().unwrap().as_u64(), 4096);
+        assert_eq!("4k".parse::<ByteSize>().unwrap().as_u64(), 4096);
+        assert_eq!("8KiB".parse::<ByteSize>().unwrap().as_u64(), 8192);
+        assert_eq!("1.5G".parse::<ByteSize>().unwrap().as_u64(), 3 << 29);
+        assert_eq!("10T".parse::<ByteSize>().unwrap().as_u64(), 10 << 40);
+        assert_eq!("none".parse::<ByteSize>().unwrap().as_u64(), 0);
+        assert!("".parse::<ByteSize>().is_err());
+        assert!("10X".parse::<ByteSize>().is_err());
+        assert!("16E".parse::<ByteSize>().is_err());
+    }
+
+    #[test]
+    fn display() {
+        assert_eq!(ByteSize::new(512).to_string(), "512");
+        assert_eq!(ByteSize::new(4096).to_string(), "4K");
+        assert_eq!(ByteSize::new(3 << 29).to_string(), "1.50G");
+        assert_eq!(ByteSize::new(21 << 29).to_string(), "10.5G");
+        assert_eq!(ByteSize::new((100 << 30) + 1).to_string(), "100G");
+        assert_eq!(ByteSize::new(10 << 40).to_string(), "10T");
+    }
+}
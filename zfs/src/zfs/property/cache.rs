@@ -0,0 +1,88 @@
+#![allow(clippy::use_self)]
+
+use std::fmt;
+use std::str;
+
+use serde::{Deserialize, Serialize};
+
+use super::*;
+
+// primarycache=all|none|metadata, secondarycache=all|none|metadata
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Cache {
+    None,
+    Metadata,
+    #[default]
+    All,
+}
+
+impl Cache {
+    pub fn as_str(&self) -> &str {
+        match self {
+            Self::None => "none",
+            Self::Metadata => "metadata",
+            Self::All => "all",
+        }
+    }
+}
+
+impl AsRef<str> for Cache {
+    fn as_ref(&self) -> &str {
+        self.as_str()
+    }
+}
+
+impl fmt::Display for Cache {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.as_str().fmt(f)
+    }
+}
+
+impl str::FromStr for Cache {
+    type Err = error::InvalidProperty;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "none" => Ok(Self::None),
+            "metadata" => Ok(Self::Metadata),
+            "all" => Ok(Self::All),
+            other => Err(error::InvalidProperty::invalid_value(other)),
+        }
+    }
+}
+
+macro_rules! numeric {
+    ($numeric:ty) => {
+        impl TryFrom<$numeric> for Cache {
+            type Error = error::InvalidProperty;
+
+            fn try_from(value: $numeric) -> Result<Self, Self::Error> {
+                match value {
+                    0 => Ok(Self::None),
+                    1 => Ok(Self::Metadata),
+                    2 => Ok(Self::All),
+                    _ => Err(error::InvalidProperty::invalid_value(value)),
+                }
+            }
+        }
+
+        impl From<Cache> for $numeric {
+            fn from(value: Cache) -> Self {
+                match value {
+                    Cache::None => 0,
+                    Cache::Metadata => 1,
+                    Cache::All => 2,
+                }
+            }
+        }
+    };
+}
+
+numeric!(i8);
+numeric!(i16);
+numeric!(i32);
+numeric!(i64);
+numeric!(u8);
+numeric!(u16);
+numeric!(u32);
+numeric!(u64);
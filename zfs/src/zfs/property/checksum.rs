@@ -7,7 +7,7 @@ use serde::{Deserialize, Serialize};
 
 use super::*;
 
-// checksum=on|off|fletcher2|fletcher4|sha256|noparity|sha512|skein|edonr
+// checksum=on|off|fletcher2|fletcher4|sha256|noparity|sha512|skein|edonr|blake3
 #[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
 pub enum CheckSum {
     #[default]
@@ -20,6 +20,8 @@ pub enum CheckSum {
     Sha512,
     Skein,
     Edonr,
+    /// Available since OpenZFS 2.2
+    Blake3,
 }
 
 impl CheckSum {
@@ -34,6 +36,7 @@ impl CheckSum {
             Self::Sha512 => "sha512",
             Self::Skein => "skein",
             Self::Edonr => "edonr",
+            Self::Blake3 => "blake3",
         }
     }
 }
@@ -64,6 +67,7 @@ impl str::FromStr for CheckSum {
             "sha512" => Ok(Self::Sha512),
             "skein" => Ok(Self::Skein),
             "edonr" => Ok(Self::Edonr),
+            "blake3" => Ok(Self::Blake3),
             other => Err(error::InvalidProperty::invalid_value(other)),
         }
     }
@@ -93,6 +97,7 @@ macro_rules! numeric {
                     11 => Self::Sha512,
                     12 => Self::Skein,
                     13 => Self::Edonr,
+                    14 => Self::Blake3,
                     _ => Self::Off,
                 }
             }
@@ -110,6 +115,7 @@ macro_rules! numeric {
                     CheckSum::Sha512 => 11,
                     CheckSum::Skein => 12,
                     CheckSum::Edonr => 13,
+                    CheckSum::Blake3 => 14,
                 }
             }
         }
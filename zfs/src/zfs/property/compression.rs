@@ -7,6 +7,128 @@ use serde::{Deserialize, Serialize};
 
 use super::*;
 
+/// Compression function takes the lower bits (`SPA_COMPRESSBITS`), the level the rest
+const COMPRESS_BITS: u32 = 7;
+const COMPRESS_MASK: u64 = (1 << COMPRESS_BITS) - 1;
+
+const ZIO_COMPRESS_ZSTD: u64 = 16;
+/// `enum zio_zstd_levels` ordinals of plain `zstd-fast` and the last `zstd-fast-N` level
+const ZIO_ZSTD_LEVEL_FAST: u64 = 102;
+const ZIO_ZSTD_LEVEL_FAST_1000: u64 = 123;
+
+const ZSTD_MAX_LEVEL: u8 = 19;
+
+const ZSTD_LEVELS: [&str; 19] = [
+    "zstd-1", "zstd-2", "zstd-3", "zstd-4", "zstd-5", "zstd-6", "zstd-7", "zstd-8", "zstd-9",
+    "zstd-10", "zstd-11", "zstd-12", "zstd-13", "zstd-14", "zstd-15", "zstd-16", "zstd-17",
+    "zstd-18", "zstd-19",
+];
+
+/// In the `enum zio_zstd_levels` order, starting at `ZIO_ZSTD_LEVEL_FAST`
+const ZSTD_FAST_LEVELS: [(u16, &str); 22] = [
+    (0, "zstd-fast"),
+    (1, "zstd-fast-1"),
+    (2, "zstd-fast-2"),
+    (3, "zstd-fast-3"),
+    (4, "zstd-fast-4"),
+    (5, "zstd-fast-5"),
+    (6, "zstd-fast-6"),
+    (7, "zstd-fast-7"),
+    (8, "zstd-fast-8"),
+    (9, "zstd-fast-9"),
+    (10, "zstd-fast-10"),
+    (20, "zstd-fast-20"),
+    (30, "zstd-fast-30"),
+    (40, "zstd-fast-40"),
+    (50, "zstd-fast-50"),
+    (60, "zstd-fast-60"),
+    (70, "zstd-fast-70"),
+    (80, "zstd-fast-80"),
+    (90, "zstd-fast-90"),
+    (100, "zstd-fast-100"),
+    (500, "zstd-fast-500"),
+    (1000, "zstd-fast-1000"),
+];
+
+/// `zstd-N` level, 1 to 19. Level 0 is plain `zstd`, the default level (3)
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(try_from = "u8", into = "u8")]
+pub struct ZstdLevel(u8);
+
+impl ZstdLevel {
+    pub fn new(level: u8) -> Result<Self, error::InvalidProperty> {
+        if level <= ZSTD_MAX_LEVEL {
+            Ok(Self(level))
+        } else {
+            Err(error::InvalidProperty::invalid_value(format!(
+                "zstd-{level}"
+            )))
+        }
+    }
+
+    pub fn level(&self) -> u8 {
+        self.0
+    }
+}
+
+impl TryFrom<u8> for ZstdLevel {
+    type Error = error::InvalidProperty;
+
+    fn try_from(level: u8) -> Result<Self, Self::Error> {
+        Self::new(level)
+    }
+}
+
+impl From<ZstdLevel> for u8 {
+    fn from(level: ZstdLevel) -> Self {
+        level.level()
+    }
+}
+
+/// `zstd-fast-N` level, N is 1 to 10, 20 to 100 in steps of 10, 500 or 1000.
+/// Level 0 is plain `zstd-fast`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(try_from = "u16", into = "u16")]
+pub struct ZstdFastLevel(u8);
+
+impl ZstdFastLevel {
+    pub fn new(level: u16) -> Result<Self, error::InvalidProperty> {
+        ZSTD_FAST_LEVELS
+            .iter()
+            .position(|(fast, _)| *fast == level)
+            .map(|index| Self(index as u8))
+            .ok_or_else(|| error::InvalidProperty::invalid_value(format!("zstd-fast-{level}")))
+    }
+
+    pub fn level(&self) -> u16 {
+        ZSTD_FAST_LEVELS[usize::from(self.0)].0
+    }
+
+    fn name(&self) -> &'static str {
+        ZSTD_FAST_LEVELS[usize::from(self.0)].1
+    }
+
+    /// `enum zio_zstd_levels` ordinal
+    fn ordinal(&self) -> u64 {
+        ZIO_ZSTD_LEVEL_FAST + u64::from(self.0)
+    }
+}
+
+impl TryFrom<u16> for ZstdFastLevel {
+    type Error = error::InvalidProperty;
+
+    fn try_from(level: u16) -> Result<Self, Self::Error> {
+        Self::new(level)
+    }
+}
+
+impl From<ZstdFastLevel> for u16 {
+    fn from(level: ZstdFastLevel) -> Self {
+        level.level()
+    }
+}
+
+// compression=on|off|lzjb|gzip|gzip-[1-9]|zle|lz4|zstd|zstd-[1-19]|zstd-fast|zstd-fast-[1-10,20..100,500,1000]
 #[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
 pub enum Compression {
     On,
@@ -25,10 +147,21 @@ pub enum Compression {
     Gzip9,
     Zle,
     Lz4,
-    Zstd,
+    Zstd(ZstdLevel),
+    ZstdFast(ZstdFastLevel),
 }
 
 impl Compression {
+    /// `zstd-N`, level 0 is plain `zstd`
+    pub fn zstd(level: u8) -> Result<Self, error::InvalidProperty> {
+        ZstdLevel::new(level).map(Self::Zstd)
+    }
+
+    /// `zstd-fast-N`, level 0 is plain `zstd-fast`
+    pub fn zstd_fast(level: u16) -> Result<Self, error::InvalidProperty> {
+        ZstdFastLevel::new(level).map(Self::ZstdFast)
+    }
+
     pub fn as_str(&self) -> &str {
         match self {
             Self::On => "on",
@@ -46,7 +179,67 @@ impl Compression {
             Self::Gzip9 => "gzip-9",
             Self::Zle => "zle",
             Self::Lz4 => "lz4",
-            Self::Zstd => "zstd",
+            Self::Zstd(level) => match level.level() {
+                0 => "zstd",
+                level => ZSTD_LEVELS[usize::from(level) - 1],
+            },
+            Self::ZstdFast(level) => level.name(),
+        }
+    }
+
+    /// `level` is the `enum zio_zstd_levels` ordinal
+    fn zstd_level(level: u64) -> Self {
+        match level {
+            level if level <= u64::from(ZSTD_MAX_LEVEL) => Self::Zstd(ZstdLevel(level as u8)),
+            ZIO_ZSTD_LEVEL_FAST..=ZIO_ZSTD_LEVEL_FAST_1000 => {
+                Self::ZstdFast(ZstdFastLevel((level - ZIO_ZSTD_LEVEL_FAST) as u8))
+            }
+            // ZIO_ZSTD_LEVEL_AUTO or a level added later, still zstd at some level
+            _ => Self::Zstd(ZstdLevel::default()),
+        }
+    }
+
+    fn encode(&self) -> u64 {
+        let zstd = |level: u64| ZIO_COMPRESS_ZSTD | level << COMPRESS_BITS;
+        match self {
+            Self::On => 1,
+            Self::Off => 2,
+            Self::Lzjb => 3,
+            Self::Gzip => 10,
+            Self::Gzip1 => 5,
+            Self::Gzip2 => 6,
+            Self::Gzip3 => 7,
+            Self::Gzip4 => 8,
+            Self::Gzip5 => 9,
+            Self::Gzip6 => 10,
+            Self::Gzip7 => 11,
+            Self::Gzip8 => 12,
+            Self::Gzip9 => 13,
+            Self::Zle => 14,
+            Self::Lz4 => 15,
+            Self::Zstd(level) => zstd(u64::from(level.level())),
+            Self::ZstdFast(level) => zstd(level.ordinal()),
+        }
+    }
+
+    fn decode(value: u64) -> Self {
+        match value & COMPRESS_MASK {
+            1 => Self::On,
+            2 => Self::Off,
+            3 => Self::Lzjb,
+            5 => Self::Gzip1,
+            6 => Self::Gzip2,
+            7 => Self::Gzip3,
+            8 => Self::Gzip4,
+            9 => Self::Gzip5,
+            10 => Self::Gzip6,
+            11 => Self::Gzip7,
+            12 => Self::Gzip8,
+            13 => Self::Gzip9,
+            14 => Self::Zle,
+            15 => Self::Lz4,
+            ZIO_COMPRESS_ZSTD => Self::zstd_level(value >> COMPRESS_BITS),
+            _ => Self::Off,
         }
     }
 }
@@ -83,8 +276,18 @@ impl str::FromStr for Compression {
             "gzip-9" => Ok(Self::Gzip9),
             "zle" => Ok(Self::Zle),
             "lz4" => Ok(Self::Lz4),
-            "zstd" => Ok(Self::Zstd),
-            other => Err(error::InvalidProperty::invalid_value(other)),
+            "zstd" => Ok(Self::Zstd(ZstdLevel::default())),
+            other => {
+                if let Some(index) = ZSTD_LEVELS.iter().position(|name| *name == other) {
+                    Ok(Self::Zstd(ZstdLevel(index as u8 + 1)))
+                } else if let Some(index) =
+                    ZSTD_FAST_LEVELS.iter().position(|(_, name)| *name == other)
+                {
+                    Ok(Self::ZstdFast(ZstdFastLevel(index as u8)))
+                } else {
+                    Err(error::InvalidProperty::invalid_value(other))
+                }
+            }
         }
     }
 }
@@ -103,57 +306,103 @@ macro_rules! numeric {
     ($numeric:ty) => {
         impl From<$numeric> for Compression {
             fn from(value: $numeric) -> Self {
-                match value {
-                    1 => Self::On,
-                    2 => Self::Off,
-                    3 => Self::Lzjb,
-                    5 => Self::Gzip1,
-                    6 => Self::Gzip2,
-                    7 => Self::Gzip3,
-                    8 => Self::Gzip4,
-                    9 => Self::Gzip5,
-                    10 => Self::Gzip6,
-                    11 => Self::Gzip7,
-                    12 => Self::Gzip8,
-                    13 => Self::Gzip9,
-                    14 => Self::Zle,
-                    15 => Self::Lz4,
-                    16 => Self::Zstd,
-                    _ => Self::Off,
-                }
+                Self::decode(value as u64)
             }
         }
+    };
+}
+
+macro_rules! wide {
+    ($numeric:ty) => {
+        numeric!($numeric);
 
         impl From<Compression> for $numeric {
             fn from(value: Compression) -> Self {
-                match value {
-                    Compression::On => 1,
-                    Compression::Off => 2,
-                    Compression::Lzjb => 3,
-                    Compression::Gzip => 10,
-                    Compression::Gzip1 => 5,
-                    Compression::Gzip2 => 6,
-                    Compression::Gzip3 => 7,
-                    Compression::Gzip4 => 8,
-                    Compression::Gzip5 => 9,
-                    Compression::Gzip6 => 10,
-                    Compression::Gzip7 => 11,
-                    Compression::Gzip8 => 12,
-                    Compression::Gzip9 => 13,
-                    Compression::Zle => 14,
-                    Compression::Lz4 => 15,
-                    Compression::Zstd => 16,
-                }
+                value.encode() as $numeric
             }
         }
     };
 }
 
-numeric!(i8);
-numeric!(i16);
-numeric!(i32);
-numeric!(i64);
-numeric!(u8);
-numeric!(u16);
-numeric!(u32);
-numeric!(u64);
+// The zstd level does not fit into the narrower types
+macro_rules! narrow {
+    ($numeric:ty) => {
+        numeric!($numeric);
+
+        impl TryFrom<Compression> for $numeric {
+            type Error = error::InvalidProperty;
+
+            fn try_from(value: Compression) -> Result<Self, Self::Error> {
+                let value = value.encode();
+                <$numeric>::try_from(value)
+                    .map_err(|_| error::InvalidProperty::invalid_value(value))
+            }
+        }
+    };
+}
+
+narrow!(i8);
+narrow!(i16);
+wide!(i32);
+wide!(i64);
+narrow!(u8);
+narrow!(u16);
+wide!(u32);
+wide!(u64);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn zstd_levels() -> Result<(), error::InvalidProperty> {
+        assert_eq!(u64::from(Compression::zstd(0)?), 16);
+        assert_eq!(u64::from(Compression::zstd(3)?), 16 | 3 << 7);
+        assert_eq!(u64::from(Compression::zstd_fast(0)?), 16 | 102 << 7);
+        assert_eq!(u64::from(Compression::zstd_fast(1)?), 16 | 103 << 7);
+        assert_eq!(u64::from(Compression::zstd_fast(20)?), 16 | 113 << 7);
+        assert_eq!(Compression::from(16 | 19 << 7_u64), Compression::zstd(19)?);
+        assert_eq!(
+            Compression::from(16 | 112 << 7_u64),
+            Compression::zstd_fast(10)?
+        );
+        assert_eq!(
+            Compression::from(16 | 122 << 7_u64),
+            Compression::zstd_fast(500)?
+        );
+        assert_eq!(
+            Compression::from(16 | 123 << 7_u64),
+            Compression::zstd_fast(1000)?
+        );
+        assert_eq!(Compression::from(16_u64), Compression::zstd(0)?);
+        assert_eq!(u8::try_from(Compression::zstd(0)?)?, 16);
+        assert!(u8::try_from(Compression::zstd(3)?).is_err());
+        assert_eq!(i16::try_from(Compression::zstd(3)?)?, 16 | 3 << 7);
+        assert!(i16::try_from(Compression::zstd_fast(1000)?).is_ok());
+        assert!(Compression::zstd(20).is_err());
+        assert!(Compression::zstd_fast(11).is_err());
+        assert!(Compression::zstd_fast(1001).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn names() {
+        for name in [
+            "zstd",
+            "zstd-1",
+            "zstd-19",
+            "zstd-fast",
+            "zstd-fast-10",
+            "zstd-fast-1000",
+            "lz4",
+        ] {
+            assert_eq!(name.parse::<Compression>().unwrap().as_str(), name);
+        }
+        assert_eq!(
+            "zstd-fast-1".parse::<Compression>().unwrap(),
+            Compression::zstd_fast(1).unwrap()
+        );
+        assert!("zstd-20".parse::<Compression>().is_err());
+        assert!("zstd-fast-11".parse::<Compression>().is_err());
+    }
+}
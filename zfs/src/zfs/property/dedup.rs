@@ -0,0 +1,132 @@
+#![allow(clippy::use_self)]
+
+use std::fmt;
+use std::str;
+
+use serde::{Deserialize, Serialize};
+
+use super::*;
+
+// dedup=off|on|verify|sha256[,verify]|sha512[,verify]|skein[,verify]|edonr,verify|blake3[,verify]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Dedup {
+    #[default]
+    Off,
+    On,
+    Verify,
+    Sha256,
+    Sha256Verify,
+    Sha512,
+    Sha512Verify,
+    Skein,
+    SkeinVerify,
+    EdonrVerify,
+    /// Available since OpenZFS 2.2
+    Blake3,
+    Blake3Verify,
+}
+
+impl Dedup {
+    pub fn as_str(&self) -> &str {
+        match self {
+            Self::Off => "off",
+            Self::On => "on",
+            Self::Verify => "verify",
+            Self::Sha256 => "sha256",
+            Self::Sha256Verify => "sha256,verify",
+            Self::Sha512 => "sha512",
+            Self::Sha512Verify => "sha512,verify",
+            Self::Skein => "skein",
+            Self::SkeinVerify => "skein,verify",
+            Self::EdonrVerify => "edonr,verify",
+            Self::Blake3 => "blake3",
+            Self::Blake3Verify => "blake3,verify",
+        }
+    }
+}
+
+impl AsRef<str> for Dedup {
+    fn as_ref(&self) -> &str {
+        self.as_str()
+    }
+}
+
+impl fmt::Display for Dedup {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.as_str().fmt(f)
+    }
+}
+
+impl str::FromStr for Dedup {
+    type Err = error::InvalidProperty;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "off" => Ok(Self::Off),
+            "on" => Ok(Self::On),
+            "verify" => Ok(Self::Verify),
+            "sha256" => Ok(Self::Sha256),
+            "sha256,verify" => Ok(Self::Sha256Verify),
+            "sha512" => Ok(Self::Sha512),
+            "sha512,verify" => Ok(Self::Sha512Verify),
+            "skein" => Ok(Self::Skein),
+            "skein,verify" => Ok(Self::SkeinVerify),
+            "edonr,verify" => Ok(Self::EdonrVerify),
+            "blake3" => Ok(Self::Blake3),
+            "blake3,verify" => Ok(Self::Blake3Verify),
+            other => Err(error::InvalidProperty::invalid_value(other)),
+        }
+    }
+}
+
+macro_rules! numeric {
+    ($numeric:ty) => {
+        impl TryFrom<$numeric> for Dedup {
+            type Error = error::InvalidProperty;
+
+            fn try_from(value: $numeric) -> Result<Self, Self::Error> {
+                match value {
+                    2 => Ok(Self::Off),
+                    1 => Ok(Self::On),
+                    257 => Ok(Self::Verify),
+                    8 => Ok(Self::Sha256),
+                    264 => Ok(Self::Sha256Verify),
+                    11 => Ok(Self::Sha512),
+                    267 => Ok(Self::Sha512Verify),
+                    12 => Ok(Self::Skein),
+                    268 => Ok(Self::SkeinVerify),
+                    269 => Ok(Self::EdonrVerify),
+                    14 => Ok(Self::Blake3),
+                    270 => Ok(Self::Blake3Verify),
+                    _ => Err(error::InvalidProperty::invalid_value(value)),
+                }
+            }
+        }
+
+        impl From<Dedup> for $numeric {
+            fn from(value: Dedup) -> Self {
+                match value {
+                    Dedup::Off => 2,
+                    Dedup::On => 1,
+                    Dedup::Verify => 257,
+                    Dedup::Sha256 => 8,
+                    Dedup::Sha256Verify => 264,
+                    Dedup::Sha512 => 11,
+                    Dedup::Sha512Verify => 267,
+                    Dedup::Skein => 12,
+                    Dedup::SkeinVerify => 268,
+                    Dedup::EdonrVerify => 269,
+                    Dedup::Blake3 => 14,
+                    Dedup::Blake3Verify => 270,
+                }
+            }
+        }
+    };
+}
+
+numeric!(i16);
+numeric!(i32);
+numeric!(i64);
+numeric!(u16);
+numeric!(u32);
+numeric!(u64);
@@ -0,0 +1,106 @@
+#![allow(clippy::use_self)]
+
+use std::fmt;
+use std::str;
+
+use serde::{Deserialize, Serialize};
+
+use super::*;
+
+// dnodesize=legacy|auto|1k|2k|4k|8k|16k
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum DnodeSize {
+    #[default]
+    Legacy,
+    Auto,
+    Size1K,
+    Size2K,
+    Size4K,
+    Size8K,
+    Size16K,
+}
+
+impl DnodeSize {
+    pub fn as_str(&self) -> &str {
+        match self {
+            Self::Legacy => "legacy",
+            Self::Auto => "auto",
+            Self::Size1K => "1k",
+            Self::Size2K => "2k",
+            Self::Size4K => "4k",
+            Self::Size8K => "8k",
+            Self::Size16K => "16k",
+        }
+    }
+}
+
+impl AsRef<str> for DnodeSize {
+    fn as_ref(&self) -> &str {
+        self.as_str()
+    }
+}
+
+impl fmt::Display for DnodeSize {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.as_str().fmt(f)
+    }
+}
+
+impl str::FromStr for DnodeSize {
+    type Err = error::InvalidProperty;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "legacy" => Ok(Self::Legacy),
+            "auto" => Ok(Self::Auto),
+            "1k" => Ok(Self::Size1K),
+            "2k" => Ok(Self::Size2K),
+            "4k" => Ok(Self::Size4K),
+            "8k" => Ok(Self::Size8K),
+            "16k" => Ok(Self::Size16K),
+            other => Err(error::InvalidProperty::invalid_value(other)),
+        }
+    }
+}
+
+macro_rules! numeric {
+    ($numeric:ty) => {
+        impl TryFrom<$numeric> for DnodeSize {
+            type Error = error::InvalidProperty;
+
+            fn try_from(value: $numeric) -> Result<Self, Self::Error> {
+                match value {
+                    0 => Ok(Self::Legacy),
+                    1 => Ok(Self::Auto),
+                    1024 => Ok(Self::Size1K),
+                    2048 => Ok(Self::Size2K),
+                    4096 => Ok(Self::Size4K),
+                    8192 => Ok(Self::Size8K),
+                    16384 => Ok(Self::Size16K),
+                    _ => Err(error::InvalidProperty::invalid_value(value)),
+                }
+            }
+        }
+
+        impl From<DnodeSize> for $numeric {
+            fn from(value: DnodeSize) -> Self {
+                match value {
+                    DnodeSize::Legacy => 0,
+                    DnodeSize::Auto => 1,
+                    DnodeSize::Size1K => 1024,
+                    DnodeSize::Size2K => 2048,
+                    DnodeSize::Size4K => 4096,
+                    DnodeSize::Size8K => 8192,
+                    DnodeSize::Size16K => 16384,
+                }
+            }
+        }
+    };
+}
+
+numeric!(i16);
+numeric!(i32);
+numeric!(i64);
+numeric!(u16);
+numeric!(u32);
+numeric!(u64);
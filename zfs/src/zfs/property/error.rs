@@ -6,14 +6,56 @@ pub enum InvalidProperty {
     NoSuchProperty(String),
     #[error("Invalid value ({0})")]
     InvalidValue(String),
+    #[error("Invalid user property name ({0})")]
+    InvalidUserProperty(String),
+    #[error("Read-only property ({0})")]
+    ReadOnly(String),
+    #[error("Property does not apply to {1} ({0})")]
+    NotApplicable(String, String),
+    #[error("Invalid value for {0} ({1})")]
+    InvalidPropertyValue(String, String),
+    #[error("Value out of range for {0}: {1}")]
+    OutOfRange(String, String),
+    #[error("{0} must be a power of 2 ({1})")]
+    NotPowerOfTwo(String, u64),
+    #[error("{0}")]
+    Rejected(String),
 }
 
 impl InvalidProperty {
-    pub(crate) fn _no_such_property(prop: impl ToString) -> Self {
+    pub(crate) fn no_such_property(prop: impl ToString) -> Self {
         Self::NoSuchProperty(prop.to_string())
     }
 
+    pub(crate) fn not_applicable(prop: impl ToString, r#type: impl ToString) -> Self {
+        Self::NotApplicable(prop.to_string(), r#type.to_string())
+    }
+
+    pub(crate) fn read_only(prop: impl ToString) -> Self {
+        Self::ReadOnly(prop.to_string())
+    }
+
+    pub(crate) fn invalid_user_property(name: impl ToString) -> Self {
+        Self::InvalidUserProperty(name.to_string())
+    }
+
     pub(crate) fn invalid_value(value: impl ToString) -> Self {
         Self::InvalidValue(value.to_string())
     }
+
+    pub(crate) fn invalid_property_value(prop: impl ToString, value: impl ToString) -> Self {
+        Self::InvalidPropertyValue(prop.to_string(), value.to_string())
+    }
+
+    pub(crate) fn out_of_range(prop: impl ToString, reason: impl ToString) -> Self {
+        Self::OutOfRange(prop.to_string(), reason.to_string())
+    }
+
+    pub(crate) fn not_power_of_two(prop: impl ToString, value: u64) -> Self {
+        Self::NotPowerOfTwo(prop.to_string(), value)
+    }
+
+    pub(crate) fn rejected(reason: impl ToString) -> Self {
+        Self::Rejected(reason.to_string())
+    }
 }
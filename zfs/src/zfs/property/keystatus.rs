@@ -0,0 +1,88 @@
+#![allow(clippy::use_self)]
+
+use std::fmt;
+use std::str;
+
+use serde::{Deserialize, Serialize};
+
+use super::*;
+
+// keystatus=none|unavailable|available
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum KeyStatus {
+    #[default]
+    None,
+    Unavailable,
+    Available,
+}
+
+impl KeyStatus {
+    pub fn as_str(&self) -> &str {
+        match self {
+            Self::None => "none",
+            Self::Unavailable => "unavailable",
+            Self::Available => "available",
+        }
+    }
+}
+
+impl AsRef<str> for KeyStatus {
+    fn as_ref(&self) -> &str {
+        self.as_str()
+    }
+}
+
+impl fmt::Display for KeyStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.as_str().fmt(f)
+    }
+}
+
+impl str::FromStr for KeyStatus {
+    type Err = error::InvalidProperty;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "none" => Ok(Self::None),
+            "unavailable" => Ok(Self::Unavailable),
+            "available" => Ok(Self::Available),
+            other => Err(error::InvalidProperty::invalid_value(other)),
+        }
+    }
+}
+
+macro_rules! numeric {
+    ($numeric:ty) => {
+        impl TryFrom<$numeric> for KeyStatus {
+            type Error = error::InvalidProperty;
+
+            fn try_from(value: $numeric) -> Result<Self, Self::Error> {
+                match value {
+                    0 => Ok(Self::None),
+                    1 => Ok(Self::Unavailable),
+                    2 => Ok(Self::Available),
+                    _ => Err(error::InvalidProperty::invalid_value(value)),
+                }
+            }
+        }
+
+        impl From<KeyStatus> for $numeric {
+            fn from(value: KeyStatus) -> Self {
+                match value {
+                    KeyStatus::None => 0,
+                    KeyStatus::Unavailable => 1,
+                    KeyStatus::Available => 2,
+                }
+            }
+        }
+    };
+}
+
+numeric!(i8);
+numeric!(i16);
+numeric!(i32);
+numeric!(i64);
+numeric!(u8);
+numeric!(u16);
+numeric!(u32);
+numeric!(u64);
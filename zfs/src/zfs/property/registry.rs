@@ -0,0 +1,262 @@
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+use libzfs::zfs_type_t;
+use libzfs::zprop_type_t;
+
+use super::*;
+
+static REGISTRY: Lazy<Registry> = Lazy::new(Registry::new);
+
+/// `SPA_MINBLOCKSIZE` and `SPA_MAXBLOCKSIZE`
+const MIN_BLOCKSIZE: u64 = 512;
+const MAX_BLOCKSIZE: u64 = 16 << 20;
+const MAX_COPIES: u64 = 3;
+
+/// All the dataset properties known to the loaded libzfs
+#[inline]
+pub fn registry() -> &'static Registry {
+    &REGISTRY
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum DatasetType {
+    Filesystem,
+    Volume,
+    Snapshot,
+    Bookmark,
+}
+
+impl DatasetType {
+    pub fn as_str(&self) -> &str {
+        match self {
+            Self::Filesystem => "filesystem",
+            Self::Volume => "volume",
+            Self::Snapshot => "snapshot",
+            Self::Bookmark => "bookmark",
+        }
+    }
+}
+
+impl fmt::Display for DatasetType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.as_str().fmt(f)
+    }
+}
+
+impl From<DatasetType> for zfs_type_t {
+    fn from(r#type: DatasetType) -> Self {
+        match r#type {
+            DatasetType::Filesystem => zfs_type_t::ZFS_TYPE_FILESYSTEM,
+            DatasetType::Volume => zfs_type_t::ZFS_TYPE_VOLUME,
+            DatasetType::Snapshot => zfs_type_t::ZFS_TYPE_SNAPSHOT,
+            DatasetType::Bookmark => zfs_type_t::ZFS_TYPE_BOOKMARK,
+        }
+    }
+}
+
+/// How the property value is stored
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum PropType {
+    Number,
+    String,
+    /// Number with the fixed set of names, e.g. `compression=lz4`
+    Index,
+}
+
+impl From<zprop_type_t> for PropType {
+    fn from(r#type: zprop_type_t) -> Self {
+        match r#type {
+            zprop_type_t::PROP_TYPE_STRING => Self::String,
+            zprop_type_t::PROP_TYPE_INDEX => Self::Index,
+            _ => Self::Number,
+        }
+    }
+}
+
+/// Static description of the dataset property
+#[derive(Clone, Debug)]
+pub struct PropInfo {
+    prop: zfs_prop_t,
+    name: Cow<'static, str>,
+    r#type: PropType,
+    readonly: bool,
+    setonce: bool,
+    inheritable: bool,
+}
+
+impl PropInfo {
+    fn new(prop: zfs_prop_t) -> Self {
+        Self {
+            prop,
+            name: prop_name(prop),
+            r#type: libzfs::zfs_prop_get_type(prop).into(),
+            readonly: libzfs::zfs_prop_readonly(prop) && !libzfs::zfs_prop_setonce(prop),
+            setonce: libzfs::zfs_prop_setonce(prop),
+            inheritable: libzfs::zfs_prop_inheritable(prop),
+        }
+    }
+
+    pub fn prop(&self) -> zfs_prop_t {
+        self.prop
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn r#type(&self) -> PropType {
+        self.r#type
+    }
+
+    pub fn readonly(&self) -> bool {
+        self.readonly
+    }
+
+    /// Can only be set when the dataset is created, e.g. `volblocksize` or `casesensitivity`
+    pub fn setonce(&self) -> bool {
+        self.setonce
+    }
+
+    pub fn inheritable(&self) -> bool {
+        self.inheritable
+    }
+
+    pub fn valid_for(&self, r#type: DatasetType) -> bool {
+        libzfs::zfs_prop_valid_for_type(self.prop, r#type.into())
+    }
+
+    /// Default value as `zfs get` shows it, empty if there is none
+    pub fn default_value(&self) -> String {
+        match self.r#type {
+            PropType::Number => libzfs::zfs_prop_default_numeric(self.prop).to_string(),
+            PropType::String => libzfs::zfs_prop_default_string(self.prop).to_string(),
+            PropType::Index => {
+                let index = libzfs::zfs_prop_default_numeric(self.prop);
+                libzfs::zfs_prop_index_to_string(self.prop, index)
+                    .unwrap_or_default()
+                    .to_string()
+            }
+        }
+    }
+
+    /// Check that `value` can be set on the dataset of the given type.
+    /// Set-once properties pass, they are valid at creation time.
+    pub fn validate(&self, value: &str, r#type: DatasetType) -> Result<(), InvalidProperty> {
+        self.check_settable(r#type)?;
+        let invalid = || InvalidProperty::invalid_property_value(self.name(), value);
+        match self.r#type {
+            PropType::Number => {
+                let value = value.parse::<ByteSize>().map_err(|_| invalid())?;
+                self.check_range(value.as_u64())
+            }
+            PropType::String => Ok(()),
+            PropType::Index => {
+                // Numeric indexes (`copies`) get the range error rather than the unknown name
+                if let Ok(number) = value.parse::<u64>() {
+                    self.check_range(number)?;
+                }
+                libzfs::zfs_prop_string_to_index(self.prop, value)
+                    .map(|_| ())
+                    .ok_or_else(invalid)
+            }
+        }
+    }
+
+    /// Same as `validate()` for the value already in its numeric form
+    pub fn validate_numeric(&self, value: u64, r#type: DatasetType) -> Result<(), InvalidProperty> {
+        self.check_settable(r#type)?;
+        self.check_range(value)?;
+        let invalid = || InvalidProperty::invalid_property_value(self.name(), value);
+        match self.r#type {
+            PropType::Number => Ok(()),
+            PropType::String => Err(invalid()),
+            PropType::Index => libzfs::zfs_prop_index_to_string(self.prop, value)
+                .map(|_| ())
+                .ok_or_else(invalid),
+        }
+    }
+
+    fn check_settable(&self, r#type: DatasetType) -> Result<(), InvalidProperty> {
+        if self.readonly {
+            Err(InvalidProperty::read_only(self.name()))
+        } else if !self.valid_for(r#type) {
+            Err(InvalidProperty::not_applicable(self.name(), r#type))
+        } else {
+            Ok(())
+        }
+    }
+
+    /// The limits `zfs_valid_proplist()` enforces, reported with the property name
+    fn check_range(&self, value: u64) -> Result<(), InvalidProperty> {
+        match self.prop {
+            zfs_prop_t::ZFS_PROP_COPIES if !(1..=MAX_COPIES).contains(&value) => Err(
+                InvalidProperty::out_of_range(self.name(), format!("{value}, must be 1 to 3")),
+            ),
+            zfs_prop_t::ZFS_PROP_RECORDSIZE | zfs_prop_t::ZFS_PROP_VOLBLOCKSIZE => {
+                if !value.is_power_of_two() {
+                    Err(InvalidProperty::not_power_of_two(self.name(), value))
+                } else if !(MIN_BLOCKSIZE..=MAX_BLOCKSIZE).contains(&value) {
+                    let reason = format!(
+                        "{}, must be {} to {}",
+                        ByteSize::new(value),
+                        ByteSize::new(MIN_BLOCKSIZE),
+                        ByteSize::new(MAX_BLOCKSIZE)
+                    );
+                    Err(InvalidProperty::out_of_range(self.name(), reason))
+                } else {
+                    Ok(())
+                }
+            }
+            _ => Ok(()),
+        }
+    }
+}
+
+/// Registry of the dataset properties, built from the libzfs property tables.
+/// It is the single source of truth for what can be set, where and to what.
+///
+#[derive(Debug)]
+pub struct Registry {
+    props: Vec<PropInfo>,
+}
+
+impl Registry {
+    fn new() -> Self {
+        let props = libzfs::zfs_props().into_iter().map(PropInfo::new).collect();
+        Self { props }
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &PropInfo> {
+        self.props.iter()
+    }
+
+    pub fn get(&self, name: impl AsRef<str>) -> Option<&PropInfo> {
+        let name = name.as_ref();
+        self.props.iter().find(|info| info.name() == name)
+    }
+
+    /// Properties that apply to the dataset of the given type
+    pub fn for_type(&self, r#type: DatasetType) -> impl Iterator<Item = &PropInfo> {
+        self.props.iter().filter(move |info| info.valid_for(r#type))
+    }
+
+    /// Check the `name=value` pair for the dataset of the given type.
+    /// User properties (`module:property`) only have their name checked.
+    pub fn validate(
+        &self,
+        name: impl AsRef<str>,
+        value: impl AsRef<str>,
+        r#type: DatasetType,
+    ) -> Result<(), InvalidProperty> {
+        let name = name.as_ref();
+        if name.contains(':') {
+            return validate_user_property(name);
+        }
+
+        self.get(name)
+            .ok_or_else(|| InvalidProperty::no_such_property(name))?
+            .validate(value.as_ref(), r#type)
+    }
+}
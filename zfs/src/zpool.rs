@@ -0,0 +1,288 @@
+use std::ffi;
+
+use libzfs::pool_scan_func_t::*;
+use libzfs::pool_scrub_cmd_t::*;
+use libzfs::zpool_config::*;
+use libzfs::zpool_prop_t::*;
+
+use super::*;
+
+use zfs::property::InvalidProperty;
+
+pub use event::Events;
+pub use event::ZfsEvent;
+pub use history::History;
+pub use history::HistoryEvent;
+pub use history::HistoryRecord;
+pub use import::ImportOptions;
+pub use import::ImportSearch;
+pub use import::ImportablePool;
+pub use import::PoolState;
+pub use scan::ScanStats;
+pub use tree::VdevState;
+pub use tree::VdevTree;
+pub use vdev::Vdev;
+pub use vdev::VdevSpec;
+
+mod device;
+pub mod event;
+mod history;
+mod import;
+pub mod property;
+pub mod scan;
+pub mod tree;
+mod vdev;
+
+const ZFS_FRAG_INVALID: u64 = u64::MAX;
+const LOAD_REWIND_POLICY: &str = "load-rewind-policy";
+
+#[derive(Debug)]
+pub struct Zpool {
+    pool: libzfs::ZpoolHandle,
+}
+
+impl Zpool {
+    pub fn get(name: impl AsRef<str>) -> Result<Self> {
+        let cname = ffi::CString::new(name.as_ref())?;
+        let pool = libzfs::ZpoolHandle::new(cname)?;
+
+        Ok(Self { pool })
+    }
+
+    /// Create new pool with `spec` vdev topology. `fs_props` are applied to the pool
+    /// root filesystem.
+    ///
+    pub fn create(
+        name: impl AsRef<str>,
+        spec: &VdevSpec,
+        pool_props: property::Properties,
+        fs_props: zfs::property::Properties,
+    ) -> Result<Self> {
+        let nvroot = spec.to_nvlist()?;
+        libzfs::zpool_create(name.as_ref(), nvroot, pool_props, fs_props)?;
+        Self::get(name)
+    }
+
+    /// Destroy this pool, all the data is lost
+    pub fn destroy(self) -> Result<()> {
+        let log = format!("zpool destroy {}", self.name());
+        self.pool.destroy(log)?;
+        Ok(())
+    }
+
+    /// Vdev tree with the vdev and scan statistics, as of the last refresh
+    pub fn vdevs(&self) -> Result<VdevTree> {
+        let config = self.pool.config();
+        let tree = config
+            .lookup_nvpair(ZPOOL_CONFIG_VDEV_TREE)?
+            .ok_or_else(DatasetError::missing_value)?;
+        VdevTree::from_nvlist(&tree.nvlist())
+    }
+
+    /// Reload the pool configuration and statistics
+    pub fn refresh(&mut self) -> Result<()> {
+        if self.pool.refresh_stats()? {
+            Err(DatasetError::pool_not_found(self.name()))
+        } else {
+            Ok(())
+        }
+    }
+
+    /// Start scrub, or resume the paused one
+    pub fn scrub_start(&self) -> Result<()> {
+        self.pool.scan(POOL_SCAN_SCRUB, POOL_SCRUB_NORMAL)?;
+        Ok(())
+    }
+
+    /// Pause the running scrub, `scrub_start` resumes it
+    pub fn scrub_pause(&self) -> Result<()> {
+        self.pool.scan(POOL_SCAN_SCRUB, POOL_SCRUB_PAUSE)?;
+        Ok(())
+    }
+
+    /// Cancel the running scrub
+    pub fn scrub_stop(&self) -> Result<()> {
+        self.pool.scan(POOL_SCAN_NONE, POOL_SCRUB_NORMAL)?;
+        Ok(())
+    }
+
+    /// Start error scrub, i.e. verify only the blocks with known errors.
+    /// Requires OpenZFS 2.2 and the `head_errlog` pool feature.
+    pub fn error_scrub(&self) -> Result<()> {
+        self.pool.scan(POOL_SCAN_ERRORSCRUB, POOL_SCRUB_NORMAL)?;
+        Ok(())
+    }
+
+    /// Progress of the current (or the last) scrub or resilver, as of the last refresh.
+    /// `None` if the pool was never scanned.
+    pub fn scan_status(&self) -> Result<Option<ScanStats>> {
+        Ok(self.vdevs()?.scan().copied())
+    }
+
+    /// Pool history, i.e. the commands run against the pool. With `internal` it includes
+    /// the internal events and ioctls as well, as `zpool history -i` does.
+    pub fn history(&self, internal: bool) -> History<'_> {
+        History::new(&self.pool, internal)
+    }
+
+    /// Find the pool by name or guid and import it
+    pub fn import(
+        pool: impl AsRef<str>,
+        search: &ImportSearch,
+        options: ImportOptions,
+    ) -> Result<Self> {
+        search.find(pool)?.import(options)
+    }
+
+    /// Export this pool. With `force` the pool is exported even if its datasets are busy.
+    pub fn export(self, force: bool) -> Result<()> {
+        let log = format!("zpool export {}", self.name());
+        self.pool.export(force, log)?;
+        Ok(())
+    }
+
+    /// All the imported zpools
+    pub fn list() -> Result<Vec<Self>> {
+        let pools = libzfs::zpool_list()?
+            .into_iter()
+            .map(|pool| Self { pool })
+            .collect();
+        Ok(pools)
+    }
+
+    pub fn name(&self) -> String {
+        self.pool.name().to_string()
+    }
+
+    #[inline]
+    pub fn health(&self) -> Result<property::Health> {
+        self.parse(ZPOOL_PROP_HEALTH)
+    }
+
+    #[inline]
+    pub fn guid(&self) -> u64 {
+        self.pool.numeric_property(ZPOOL_PROP_GUID)
+    }
+
+    #[inline]
+    pub fn size(&self) -> u64 {
+        self.pool.numeric_property(ZPOOL_PROP_SIZE)
+    }
+
+    #[inline]
+    pub fn allocated(&self) -> u64 {
+        self.pool.numeric_property(ZPOOL_PROP_ALLOCATED)
+    }
+
+    #[inline]
+    pub fn free(&self) -> u64 {
+        self.pool.numeric_property(ZPOOL_PROP_FREE)
+    }
+
+    #[inline]
+    pub fn freeing(&self) -> u64 {
+        self.pool.numeric_property(ZPOOL_PROP_FREEING)
+    }
+
+    #[inline]
+    pub fn leaked(&self) -> u64 {
+        self.pool.numeric_property(ZPOOL_PROP_LEAKED)
+    }
+
+    /// Fragmentation percentage, `None` if not available
+    #[inline]
+    pub fn fragmentation(&self) -> Option<u64> {
+        let fragmentation = self.pool.numeric_property(ZPOOL_PROP_FRAGMENTATION);
+        (fragmentation != ZFS_FRAG_INVALID).then_some(fragmentation)
+    }
+
+    /// Used capacity percentage
+    #[inline]
+    pub fn capacity(&self) -> u64 {
+        self.pool.numeric_property(ZPOOL_PROP_CAPACITY)
+    }
+
+    /// Deduplication ratio, in hundredths (i.e. 100 means 1.00x)
+    #[inline]
+    pub fn dedupratio(&self) -> u64 {
+        self.pool.numeric_property(ZPOOL_PROP_DEDUPRATIO)
+    }
+
+    #[inline]
+    pub fn expandsize(&self) -> Result<property::Expandsize> {
+        self.parse(ZPOOL_PROP_EXPANDSZ)
+    }
+
+    #[inline]
+    pub fn ashift(&self) -> Result<property::Ashift> {
+        self.parse(ZPOOL_PROP_ASHIFT)
+    }
+
+    #[inline]
+    pub fn altroot(&self) -> Result<property::Altroot> {
+        self.parse(ZPOOL_PROP_ALTROOT)
+    }
+
+    #[inline]
+    pub fn bootfs(&self) -> Result<property::Bootfs> {
+        self.parse(ZPOOL_PROP_BOOTFS)
+    }
+
+    #[inline]
+    pub fn cachefile(&self) -> Result<property::Cachefile> {
+        self.parse(ZPOOL_PROP_CACHEFILE)
+    }
+
+    #[inline]
+    pub fn failmode(&self) -> Result<property::Failmode> {
+        self.parse(ZPOOL_PROP_FAILUREMODE)
+    }
+
+    #[inline]
+    pub fn version(&self) -> Result<property::Version> {
+        self.parse(ZPOOL_PROP_VERSION)
+    }
+
+    #[inline]
+    pub fn autoexpand(&self) -> Result<property::OnOff> {
+        self.parse(ZPOOL_PROP_AUTOEXPAND)
+    }
+
+    #[inline]
+    pub fn autoreplace(&self) -> Result<property::OnOff> {
+        self.parse(ZPOOL_PROP_AUTOREPLACE)
+    }
+
+    #[inline]
+    pub fn autotrim(&self) -> Result<property::OnOff> {
+        self.parse(ZPOOL_PROP_AUTOTRIM)
+    }
+
+    #[inline]
+    pub fn delegation(&self) -> Result<property::OnOff> {
+        self.parse(ZPOOL_PROP_DELEGATION)
+    }
+
+    #[inline]
+    pub fn listsnapshots(&self) -> Result<property::OnOff> {
+        self.parse(ZPOOL_PROP_LISTSNAPS)
+    }
+
+    #[inline]
+    pub fn multihost(&self) -> Result<property::OnOff> {
+        self.parse(ZPOOL_PROP_MULTIHOST)
+    }
+
+    #[inline]
+    pub fn readonly(&self) -> Result<property::OnOff> {
+        self.parse(ZPOOL_PROP_READONLY)
+    }
+
+    fn parse<T>(&self, property: libzfs::zpool_prop_t) -> Result<T>
+    where
+        T: std::str::FromStr<Err = InvalidProperty>,
+    {
+        let value = self.pool.string_property(property, true)?;
+        Ok(value.parse()?)
+    }
+}
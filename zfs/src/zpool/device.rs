@@ -0,0 +1,122 @@
+use std::path::Path;
+
+use libzfs::zfs_error;
+
+use super::*;
+
+const TYPE_HOLE: &str = "hole";
+const TYPE_INDIRECT: &str = "indirect";
+
+/// Device management. Existing devices are identified by path (or the last path
+/// component) or by (decimal) guid, as accepted by `zpool`.
+///
+impl Zpool {
+    /// Add the `spec` vdevs to the pool. As `zpool add` does, the data vdevs with lesser
+    /// redundancy than the pool already has are refused unless `force` is set.
+    pub fn add(&self, spec: &VdevSpec, force: bool) -> Result<()> {
+        let nvroot = spec.nvroot()?;
+        if !force {
+            let vdevs = self.vdevs()?;
+            let current = vdevs
+                .root()
+                .children
+                .iter()
+                .filter(|vdev| !vdev.log && vdev.kind != TYPE_HOLE && vdev.kind != TYPE_INDIRECT)
+                .map(tree::Vdev::redundancy)
+                .min()
+                .unwrap_or_default();
+            if let Some(vdev) = spec.data_vdevs().find(|vdev| vdev.redundancy() < current) {
+                return Err(DatasetError::reduced_redundancy(format!(
+                    "{vdev:?} has lesser redundancy than the existing vdevs"
+                )));
+            }
+        }
+        self.pool
+            .add(nvroot)
+            .map_err(|error| device_error(self.name(), error))
+    }
+
+    /// Attach `new` device to the `device`, turning it into a mirror (or widening the mirror)
+    pub fn attach(&self, device: impl AsRef<str>, new: impl AsRef<Path>) -> Result<()> {
+        self.attach_impl(device.as_ref(), new.as_ref(), false)
+    }
+
+    /// Replace `device` with the `new` one. The old device is detached once resilvered.
+    pub fn replace(&self, device: impl AsRef<str>, new: impl AsRef<Path>) -> Result<()> {
+        self.attach_impl(device.as_ref(), new.as_ref(), true)
+    }
+
+    /// Detach `device` from its mirror
+    pub fn detach(&self, device: impl AsRef<str>) -> Result<()> {
+        let device = device.as_ref();
+        self.pool
+            .vdev_detach(device)
+            .map_err(|error| device_error(device, error))
+    }
+
+    /// Bring `device` online, with `expand` it grows to use all the available space.
+    /// Returns the new device state.
+    pub fn online(&self, device: impl AsRef<str>, expand: bool) -> Result<VdevState> {
+        let device = device.as_ref();
+        let flags = if expand { libzfs::ZFS_ONLINE_EXPAND } else { 0 };
+        let state = self
+            .pool
+            .vdev_online(device, flags)
+            .map_err(|error| device_error(device, error))?;
+        Ok(VdevState::from(state as u64))
+    }
+
+    /// Take `device` offline. `temporary` offline state is reverted on reboot.
+    pub fn offline(&self, device: impl AsRef<str>, temporary: bool) -> Result<()> {
+        let device = device.as_ref();
+        self.pool
+            .vdev_offline(device, temporary)
+            .map_err(|error| device_error(device, error))
+    }
+
+    /// Clear the error counters of `device`, or of all the devices if `None`
+    pub fn clear(&self, device: Option<&str>) -> Result<()> {
+        let mut policy = nvpair::NvList::new();
+        policy.add_uint32(LOAD_REWIND_POLICY, libzfs::ZPOOL_NO_REWIND)?;
+        self.pool
+            .clear(device, policy)
+            .map_err(|error| device_error(device.unwrap_or_default(), error))
+    }
+
+    /// Remove top-level vdev, cache device or spare. Data of the removed top-level vdev
+    /// is copied to the remaining vdevs in the background.
+    pub fn remove(&self, device: impl AsRef<str>) -> Result<()> {
+        let device = device.as_ref();
+        self.pool
+            .vdev_remove(device)
+            .map_err(|error| device_error(device, error))
+    }
+
+    /// Stop the device removal in progress
+    pub fn remove_cancel(&self) -> Result<()> {
+        self.pool
+            .vdev_remove_cancel()
+            .map_err(|error| device_error(self.name(), error))
+    }
+
+    fn attach_impl(&self, device: &str, new: &Path, replacing: bool) -> Result<()> {
+        let nvroot = VdevSpec::new().device(new).to_nvlist()?;
+        let path = new
+            .to_str()
+            .ok_or_else(|| DatasetError::invalid_vdev_spec(new.display().to_string()))?;
+        self.pool
+            .vdev_attach(device, path, nvroot, replacing)
+            .map_err(|error| device_error(device, error))
+    }
+}
+
+/// Typed errors for the libzfs error codes, anything else stays `CoreErr`
+fn device_error(device: impl AsRef<str>, error: libzfs::ZfsError) -> DatasetError {
+    let device = device.as_ref();
+    match error.code() {
+        zfs_error::EZFS_BUSY => DatasetError::device_busy(device),
+        zfs_error::EZFS_NOREPLICAS => DatasetError::reduced_redundancy(device),
+        zfs_error::EZFS_ASHIFT_MISMATCH => DatasetError::ashift_mismatch(device),
+        _ => error.into(),
+    }
+}
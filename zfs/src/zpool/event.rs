@@ -0,0 +1,332 @@
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use super::*;
+
+const EREPORT: &str = "ereport.fs.zfs.";
+const RESOURCE: &str = "resource.fs.zfs.";
+const SYSEVENT: &str = "sysevent.fs.zfs.";
+
+const EVENT_CLASS: &str = "class";
+const EVENT_EID: &str = "eid";
+const EVENT_TIME: &str = "time";
+const EVENT_POOL: &str = "pool";
+const EVENT_POOL_NAME: &str = "pool_name";
+const EVENT_POOL_GUID: &str = "pool_guid";
+const EVENT_VDEV_GUID: &str = "vdev_guid";
+const EVENT_VDEV_PATH: &str = "vdev_path";
+const EVENT_VDEV_STATE: &str = "vdev_state";
+const EVENT_VDEV_LASTSTATE: &str = "vdev_laststate";
+
+/// ZFS event, as shown by `zpool events`
+///
+#[derive(Debug)]
+pub enum ZfsEvent {
+    /// Fault report, e.g. I/O or checksum error
+    Ereport(Ereport, EventInfo),
+    /// Change of the (vdev) resource state
+    Resource(Resource, EventInfo),
+    /// Notification of the pool activity, e.g. scrub completion
+    Sysevent(Sysevent, EventInfo),
+    /// Event of some other class
+    Other(EventInfo),
+}
+
+impl ZfsEvent {
+    fn from_nvlist(payload: nvpair::NvList) -> Result<Self> {
+        let info = EventInfo::from_nvlist(payload)?;
+        let event = if let Some(subclass) = info.class.strip_prefix(EREPORT) {
+            Self::Ereport(Ereport::from(subclass), info)
+        } else if let Some(subclass) = info.class.strip_prefix(RESOURCE) {
+            Self::Resource(Resource::new(subclass, &info.payload)?, info)
+        } else if let Some(subclass) = info.class.strip_prefix(SYSEVENT) {
+            Self::Sysevent(Sysevent::from(subclass), info)
+        } else {
+            Self::Other(info)
+        };
+        Ok(event)
+    }
+
+    pub fn info(&self) -> &EventInfo {
+        match self {
+            Self::Ereport(_, info)
+            | Self::Resource(_, info)
+            | Self::Sysevent(_, info)
+            | Self::Other(info) => info,
+        }
+    }
+}
+
+/// Fields common to all the events, along with the complete event payload
+///
+#[derive(Debug)]
+pub struct EventInfo {
+    /// Full event class, e.g. `ereport.fs.zfs.checksum`
+    pub class: String,
+    /// Event id, unique since the module load
+    pub eid: u64,
+    pub time: SystemTime,
+    pub pool: Option<String>,
+    pub pool_guid: Option<u64>,
+    pub vdev_guid: Option<u64>,
+    pub vdev_path: Option<String>,
+    pub payload: nvpair::NvList,
+}
+
+impl EventInfo {
+    fn from_nvlist(payload: nvpair::NvList) -> Result<Self> {
+        let class = payload
+            .lookup_nvpair(EVENT_CLASS)?
+            .ok_or_else(DatasetError::missing_value)?
+            .string()
+            .into_owned();
+        let eid = payload
+            .lookup_nvpair(EVENT_EID)?
+            .map_or(0, |eid| eid.uint64());
+        let time = payload
+            .lookup_nvpair(EVENT_TIME)?
+            .and_then(|time| match time.int64_array() {
+                [secs, nanos, ..] => Some(Duration::new(*secs as u64, *nanos as u32)),
+                _ => None,
+            })
+            .map_or(UNIX_EPOCH, |time| UNIX_EPOCH + time);
+        // ereports and sysevents name the pool differently
+        let pool = match payload.lookup_nvpair(EVENT_POOL)? {
+            Some(pool) => Some(pool),
+            None => payload.lookup_nvpair(EVENT_POOL_NAME)?,
+        }
+        .map(|pool| pool.string().into_owned());
+        let pool_guid = payload
+            .lookup_nvpair(EVENT_POOL_GUID)?
+            .map(|guid| guid.uint64());
+        let vdev_guid = payload
+            .lookup_nvpair(EVENT_VDEV_GUID)?
+            .map(|guid| guid.uint64());
+        let vdev_path = payload
+            .lookup_nvpair(EVENT_VDEV_PATH)?
+            .map(|path| path.string().into_owned());
+
+        Ok(Self {
+            class,
+            eid,
+            time,
+            pool,
+            pool_guid,
+            vdev_guid,
+            vdev_path,
+            payload,
+        })
+    }
+}
+
+/// `ereport.fs.zfs.*` event classes
+///
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Ereport {
+    Checksum,
+    Io,
+    Data,
+    Delay,
+    Deadman,
+    ProbeFailure,
+    LogReplay,
+    ConfigCacheWrite,
+    Authentication,
+    /// Vdev failure, e.g. `vdev.open_failed`
+    Vdev(String),
+    Zpool,
+    Other(String),
+}
+
+impl From<&str> for Ereport {
+    fn from(subclass: &str) -> Self {
+        match subclass {
+            "checksum" => Self::Checksum,
+            "io" => Self::Io,
+            "data" => Self::Data,
+            "delay" => Self::Delay,
+            "deadman" => Self::Deadman,
+            "probe_failure" => Self::ProbeFailure,
+            "log_replay" => Self::LogReplay,
+            "config_cache_write" => Self::ConfigCacheWrite,
+            "authentication" => Self::Authentication,
+            "zpool" => Self::Zpool,
+            other => match other.strip_prefix("vdev.") {
+                Some(vdev) => Self::Vdev(vdev.to_string()),
+                None => Self::Other(other.to_string()),
+            },
+        }
+    }
+}
+
+/// `resource.fs.zfs.*` event classes
+///
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Resource {
+    /// Vdev state change
+    StateChange {
+        state: VdevState,
+        previous: VdevState,
+    },
+    Removed,
+    Autoreplace,
+    Other(String),
+}
+
+impl Resource {
+    fn new(subclass: &str, payload: &nvpair::NvList) -> Result<Self> {
+        let state = |name: &str| -> Result<VdevState> {
+            let state = payload
+                .lookup_nvpair(name)?
+                .map_or(VdevState::Unknown, |state| state.uint64().into());
+            Ok(state)
+        };
+        let resource = match subclass {
+            "statechange" => Self::StateChange {
+                state: state(EVENT_VDEV_STATE)?,
+                previous: state(EVENT_VDEV_LASTSTATE)?,
+            },
+            "removed" => Self::Removed,
+            "autoreplace" => Self::Autoreplace,
+            other => Self::Other(other.to_string()),
+        };
+        Ok(resource)
+    }
+}
+
+/// `sysevent.fs.zfs.*` event classes
+///
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Sysevent {
+    ScrubStart,
+    ScrubFinish,
+    ScrubAbort,
+    ScrubResume,
+    ScrubPaused,
+    ResilverStart,
+    ResilverFinish,
+    PoolCreate,
+    PoolDestroy,
+    PoolImport,
+    PoolExport,
+    PoolReguid,
+    VdevAdd,
+    VdevAttach,
+    VdevClear,
+    VdevOnline,
+    VdevRemove,
+    VdevSpare,
+    VdevAutoexpand,
+    ConfigSync,
+    HistoryEvent,
+    TrimStart,
+    TrimFinish,
+    Other(String),
+}
+
+impl From<&str> for Sysevent {
+    fn from(subclass: &str) -> Self {
+        match subclass {
+            "scrub_start" => Self::ScrubStart,
+            "scrub_finish" => Self::ScrubFinish,
+            "scrub_abort" => Self::ScrubAbort,
+            "scrub_resume" => Self::ScrubResume,
+            "scrub_paused" => Self::ScrubPaused,
+            "resilver_start" => Self::ResilverStart,
+            "resilver_finish" => Self::ResilverFinish,
+            "pool_create" => Self::PoolCreate,
+            "pool_destroy" => Self::PoolDestroy,
+            "pool_import" => Self::PoolImport,
+            "pool_export" => Self::PoolExport,
+            "pool_reguid" => Self::PoolReguid,
+            "vdev_add" => Self::VdevAdd,
+            "vdev_attach" => Self::VdevAttach,
+            "vdev_clear" => Self::VdevClear,
+            "vdev_online" => Self::VdevOnline,
+            "vdev_remove" => Self::VdevRemove,
+            "vdev_spare" => Self::VdevSpare,
+            "vdev_autoexpand" => Self::VdevAutoexpand,
+            "config_sync" => Self::ConfigSync,
+            "history_event" => Self::HistoryEvent,
+            "trim_start" => Self::TrimStart,
+            "trim_finish" => Self::TrimFinish,
+            other => Self::Other(other.to_string()),
+        }
+    }
+}
+
+/// Blocking iterator over the ZFS events. Starts with the events still kept in the kernel
+/// queue, unless moved to the end with `skip_existing()`. With `follow` (the default)
+/// waits for the new events, as `zpool events -f` does, otherwise stops once the queue
+/// is drained.
+///
+#[derive(Debug)]
+pub struct Events {
+    handle: libzfs::ZeventHandle,
+    follow: bool,
+    dropped: u64,
+    done: bool,
+}
+
+impl Events {
+    pub fn new() -> Result<Self> {
+        let handle = libzfs::ZeventHandle::new()?;
+        Ok(Self {
+            handle,
+            follow: true,
+            dropped: 0,
+            done: false,
+        })
+    }
+
+    pub fn follow(mut self, follow: bool) -> Self {
+        self.follow = follow;
+        self
+    }
+
+    /// Only report the events posted from now on
+    pub fn skip_existing(self) -> Result<Self> {
+        self.handle.seek(libzfs::ZEVENT_SEEK_END)?;
+        Ok(self)
+    }
+
+    /// Continue from the event `eid` (e.g. the last one seen before restart)
+    pub fn seek(self, eid: u64) -> Result<Self> {
+        self.handle.seek(eid)?;
+        Ok(self)
+    }
+
+    /// Number of events lost so far because the kernel queue overflowed
+    pub fn dropped(&self) -> u64 {
+        self.dropped
+    }
+
+    /// Clear the kernel event queue, for all the readers. Returns the number of cleared events.
+    pub fn clear() -> Result<u32> {
+        let count = libzfs::zpool_events_clear()?;
+        Ok(count)
+    }
+
+    /// Failure to read the next event ends the iteration, the error is reported once
+    fn next_event(&mut self) -> Result<Option<ZfsEvent>> {
+        if self.done {
+            return Ok(None);
+        }
+        let next = self.handle.next(self.follow);
+        self.done = next.is_err();
+        match next? {
+            Some((payload, dropped)) => {
+                self.dropped += u64::from(dropped);
+                ZfsEvent::from_nvlist(payload).map(Some)
+            }
+            None => Ok(None),
+        }
+    }
+}
+
+impl Iterator for Events {
+    type Item = Result<ZfsEvent>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.next_event().transpose()
+    }
+}
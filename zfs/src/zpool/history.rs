@@ -0,0 +1,154 @@
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use std::vec;
+
+use libzfs::zpool_hist::*;
+
+use super::*;
+
+/// Pool history (`zpool history`), oldest records first. The history is read lazily,
+/// a chunk at a time.
+///
+#[derive(Debug)]
+pub struct History<'a> {
+    pool: &'a libzfs::ZpoolHandle,
+    internal: bool,
+    offset: u64,
+    eof: bool,
+    records: vec::IntoIter<Result<HistoryRecord>>,
+}
+
+impl<'a> History<'a> {
+    pub(crate) fn new(pool: &'a libzfs::ZpoolHandle, internal: bool) -> Self {
+        Self {
+            pool,
+            internal,
+            offset: 0,
+            eof: false,
+            records: Vec::new().into_iter(),
+        }
+    }
+
+    fn next_chunk(&mut self) -> Result<()> {
+        let (chunk, eof) = self.pool.history(&mut self.offset)?;
+        self.eof = eof;
+        let records = match chunk.lookup_nvpair(ZPOOL_HIST_RECORD)? {
+            Some(records) => records
+                .nvlist_array()
+                .iter()
+                .map(HistoryRecord::from_nvlist)
+                .filter(|record| match record {
+                    Ok(record) => self.internal || matches!(record.event, HistoryEvent::Command(_)),
+                    Err(_) => true,
+                })
+                .collect(),
+            None => Vec::new(),
+        };
+        self.records = records.into_iter();
+        Ok(())
+    }
+}
+
+impl Iterator for History<'_> {
+    type Item = Result<HistoryRecord>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some(record) = self.records.next() {
+                return Some(record);
+            }
+            if self.eof {
+                return None;
+            }
+            if let Err(error) = self.next_chunk() {
+                self.eof = true;
+                return Some(Err(error));
+            }
+        }
+    }
+}
+
+/// Single pool history record
+///
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct HistoryRecord {
+    pub time: SystemTime,
+    pub event: HistoryEvent,
+    /// Transaction group of the internal event
+    pub txg: Option<u64>,
+    /// Id of the user who issued the command
+    pub uid: Option<u64>,
+    pub host: Option<String>,
+    pub zone: Option<String>,
+}
+
+impl HistoryRecord {
+    fn from_nvlist<T>(nvl: &nvpair::NvListRef<'_, T>) -> Result<Self> {
+        let string = |name: &str| -> Result<Option<String>> {
+            let value = nvl
+                .lookup_nvpair(name)?
+                .map(|value| value.string().into_owned());
+            Ok(value)
+        };
+        let uint64 = |name: &str| -> Result<Option<u64>> {
+            let value = nvl.lookup_nvpair(name)?.map(|value| value.uint64());
+            Ok(value)
+        };
+        let int64 = |name: &str| -> Result<Option<i64>> {
+            let value = nvl.lookup_nvpair(name)?.map(|value| value.int64());
+            Ok(value)
+        };
+
+        let time = uint64(ZPOOL_HIST_TIME)?
+            .map_or(UNIX_EPOCH, |time| UNIX_EPOCH + Duration::from_secs(time));
+        let event = if let Some(command) = string(ZPOOL_HIST_CMD)? {
+            HistoryEvent::Command(command)
+        } else if let Some(name) = string(ZPOOL_HIST_INT_NAME)? {
+            HistoryEvent::Internal {
+                name,
+                dataset: string(ZPOOL_HIST_DSNAME)?,
+                details: string(ZPOOL_HIST_INT_STR)?.unwrap_or_default(),
+            }
+        } else if let Some(name) = string(ZPOOL_HIST_IOCTL)? {
+            HistoryEvent::Ioctl {
+                name,
+                errno: int64(ZPOOL_HIST_ERRNO)?,
+            }
+        } else if let Some(event) = uint64(ZPOOL_HIST_INT_EVENT)? {
+            // Pre-2013 internal events only have the event number
+            HistoryEvent::Internal {
+                name: format!("event {event}"),
+                dataset: None,
+                details: string(ZPOOL_HIST_INT_STR)?.unwrap_or_default(),
+            }
+        } else {
+            HistoryEvent::Unknown
+        };
+
+        Ok(Self {
+            time,
+            event,
+            txg: uint64(ZPOOL_HIST_TXG)?,
+            uid: uint64(ZPOOL_HIST_WHO)?,
+            host: string(ZPOOL_HIST_HOST)?,
+            zone: string(ZPOOL_HIST_ZONE)?,
+        })
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum HistoryEvent {
+    /// User command, e.g. `zpool create tank sda`
+    Command(String),
+    /// Internal event (`zpool history -i`), e.g. `set` of the dataset property
+    Internal {
+        name: String,
+        dataset: Option<String>,
+        details: String,
+    },
+    /// ioctl issued by the user command (`zpool history -i`)
+    Ioctl {
+        name: String,
+        errno: Option<i64>,
+    },
+    Unknown,
+}
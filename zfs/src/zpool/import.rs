@@ -0,0 +1,287 @@
+use std::path::Path;
+
+use libzfs::pool_state_t::*;
+use libzfs::zpool_config::*;
+
+use super::*;
+
+const LOAD_POLICY: &str = "load-policy";
+
+/// Where to look for importable pools. By default the usual device directories are scanned;
+/// directories holding file vdevs have to be added explicitly.
+///
+#[derive(Debug, Default)]
+pub struct ImportSearch {
+    dirs: Vec<String>,
+    cachefile: Option<String>,
+    scan: bool,
+    destroyed: bool,
+}
+
+impl ImportSearch {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Search `path` directory (or device) for pool devices
+    pub fn dir(mut self, path: impl AsRef<Path>) -> Self {
+        self.dirs.push(path.as_ref().to_string_lossy().into_owned());
+        self
+    }
+
+    /// Read pool configurations from `path` cachefile instead of scanning devices
+    pub fn cachefile(mut self, path: impl AsRef<Path>) -> Self {
+        self.cachefile = Some(path.as_ref().to_string_lossy().into_owned());
+        self
+    }
+
+    /// Scan all the devices rather than rely on the libblkid cache
+    pub fn scan(mut self, scan: bool) -> Self {
+        self.scan = scan;
+        self
+    }
+
+    /// Look for destroyed pools only, as `zpool import -D` does. Destroyed pools are
+    /// skipped otherwise.
+    pub fn destroyed(mut self, destroyed: bool) -> Self {
+        self.destroyed = destroyed;
+        self
+    }
+
+    /// All the pools available for import
+    pub fn search(&self) -> Result<Vec<ImportablePool>> {
+        self.search_impl(None, None)
+    }
+
+    /// The pool with the given name or (decimal) guid, as accepted by `zpool import`
+    pub fn find(&self, pool: impl AsRef<str>) -> Result<ImportablePool> {
+        let pool = pool.as_ref();
+        let mut pools = match pool.parse::<u64>() {
+            Ok(guid) => self.search_impl(None, Some(guid))?,
+            Err(_) => self.search_impl(Some(pool), None)?,
+        };
+
+        match pools.len() {
+            0 => Err(DatasetError::pool_not_found(pool)),
+            1 => Ok(pools.remove(0)),
+            _ => Err(DatasetError::ambiguous_pool(pool)),
+        }
+    }
+
+    fn search_impl(
+        &self,
+        poolname: Option<&str>,
+        guid: Option<u64>,
+    ) -> Result<Vec<ImportablePool>> {
+        let args = libzfs::ImportArgs {
+            paths: self.dirs.clone(),
+            poolname: poolname.map(ToString::to_string),
+            guid,
+            cachefile: self.cachefile.clone(),
+            scan: self.scan,
+            ..libzfs::ImportArgs::default()
+        };
+
+        let pools = libzfs::zpool_search_import(&args)?
+            .iter()
+            .map(|pool| ImportablePool::from_config(pool.nvlist().dup()?))
+            .collect::<Result<Vec<_>>>()?
+            .into_iter()
+            .filter(|pool| (pool.state == PoolState::Destroyed) == self.destroyed)
+            .collect();
+        Ok(pools)
+    }
+}
+
+/// Pool found by [`ImportSearch`]
+///
+#[derive(Debug)]
+pub struct ImportablePool {
+    name: String,
+    guid: u64,
+    state: PoolState,
+    vdevs: VdevTree,
+    config: nvpair::NvList,
+}
+
+impl ImportablePool {
+    fn from_config(config: nvpair::NvList) -> Result<Self> {
+        let nvl = nvpair::NvListRef::from_raw(*config, &config);
+        let name = nvl
+            .lookup_nvpair(ZPOOL_CONFIG_POOL_NAME)?
+            .ok_or_else(DatasetError::missing_value)?
+            .string()
+            .into_owned();
+        let guid = nvl
+            .lookup_nvpair(ZPOOL_CONFIG_POOL_GUID)?
+            .ok_or_else(DatasetError::missing_value)?
+            .uint64();
+        let state = nvl
+            .lookup_nvpair(ZPOOL_CONFIG_POOL_STATE)?
+            .map_or(PoolState::Unknown, |state| state.uint64().into());
+        let vdevs = nvl
+            .lookup_nvpair(ZPOOL_CONFIG_VDEV_TREE)?
+            .ok_or_else(DatasetError::missing_value)?;
+        let vdevs = VdevTree::from_nvlist(&vdevs.nvlist())?;
+
+        Ok(Self {
+            name,
+            guid,
+            state,
+            vdevs,
+            config,
+        })
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn guid(&self) -> u64 {
+        self.guid
+    }
+
+    pub fn state(&self) -> PoolState {
+        self.state
+    }
+
+    pub fn vdevs(&self) -> &VdevTree {
+        &self.vdevs
+    }
+
+    pub fn health(&self) -> property::Health {
+        self.vdevs.root().health()
+    }
+
+    /// Import this pool
+    pub fn import(mut self, mut options: ImportOptions) -> Result<Zpool> {
+        let mut policy = nvpair::NvList::new();
+        policy.add_uint32(LOAD_REWIND_POLICY, options.rewind_policy())?;
+        self.config.add_nvlist(LOAD_POLICY, &policy)?;
+
+        let flags = if options.force {
+            libzfs::ZFS_IMPORT_ANY_HOST
+        } else {
+            libzfs::ZFS_IMPORT_NORMAL
+        };
+        let newname = options.newname.take();
+        let name = newname.as_deref().unwrap_or(&self.name).to_string();
+        let props = options.props();
+        libzfs::zpool_import(&self.config, newname.as_deref(), props, flags as i32)?;
+        Zpool::get(name)
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PoolState {
+    Active,
+    Exported,
+    Destroyed,
+    Spare,
+    L2cache,
+    Uninitialized,
+    Unavail,
+    PotentiallyActive,
+    Unknown,
+}
+
+impl From<u64> for PoolState {
+    fn from(state: u64) -> Self {
+        match state {
+            x if x == POOL_STATE_ACTIVE as u64 => Self::Active,
+            x if x == POOL_STATE_EXPORTED as u64 => Self::Exported,
+            x if x == POOL_STATE_DESTROYED as u64 => Self::Destroyed,
+            x if x == POOL_STATE_SPARE as u64 => Self::Spare,
+            x if x == POOL_STATE_L2CACHE as u64 => Self::L2cache,
+            x if x == POOL_STATE_UNINITIALIZED as u64 => Self::Uninitialized,
+            x if x == POOL_STATE_UNAVAIL as u64 => Self::Unavail,
+            x if x == POOL_STATE_POTENTIALLY_ACTIVE as u64 => Self::PotentiallyActive,
+            _ => Self::Unknown,
+        }
+    }
+}
+
+/// Pool import options
+///
+#[derive(Debug, Default)]
+pub struct ImportOptions {
+    newname: Option<String>,
+    altroot: Option<String>,
+    cachefile: Option<String>,
+    props: property::Properties,
+    rewind: bool,
+    force: bool,
+}
+
+impl ImportOptions {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Import the pool under the `name`
+    pub fn newname(mut self, name: impl AsRef<str>) -> Self {
+        self.newname = Some(name.as_ref().to_string());
+        self
+    }
+
+    pub fn readonly(mut self, readonly: bool) -> Self {
+        self.props.readonly(readonly);
+        self
+    }
+
+    /// Import with the alternate root. As with `zpool import -R`, the pool is not added
+    /// to the cachefile unless the cachefile is set explicitly.
+    pub fn altroot(mut self, altroot: impl AsRef<str>) -> Self {
+        self.altroot = Some(altroot.as_ref().to_string());
+        self
+    }
+
+    pub fn cachefile(mut self, cachefile: impl AsRef<str>) -> Self {
+        self.cachefile = Some(cachefile.as_ref().to_string());
+        self
+    }
+
+    /// Any other pool property
+    pub fn property(mut self, name: impl AsRef<str>, value: impl AsRef<str>) -> Self {
+        self.props.string_property(name, value);
+        self
+    }
+
+    /// Roll back to the last consistent txg if the pool cannot be imported otherwise.
+    /// The last few transactions are lost.
+    pub fn rewind(mut self, rewind: bool) -> Self {
+        self.rewind = rewind;
+        self
+    }
+
+    /// Import even if the pool appears to be in use by another system
+    pub fn force(mut self, force: bool) -> Self {
+        self.force = force;
+        self
+    }
+
+    fn props(self) -> property::Properties {
+        let mut props = self.props;
+        if let Some(altroot) = &self.altroot {
+            props.altroot(altroot);
+        }
+        match (&self.cachefile, &self.altroot) {
+            (Some(cachefile), _) => {
+                props.cachefile(cachefile);
+            }
+            (None, Some(_)) => {
+                props.cachefile("none");
+            }
+            (None, None) => (),
+        }
+        props
+    }
+
+    fn rewind_policy(&self) -> u32 {
+        if self.rewind {
+            libzfs::ZPOOL_DO_REWIND
+        } else {
+            libzfs::ZPOOL_NO_REWIND
+        }
+    }
+}
@@ -1,6 +1,18 @@
 pub use allocated::Allocated;
+pub use altroot::Altroot;
+pub use ashift::Ashift;
+pub use bootfs::Bootfs;
+pub use cachefile::Cachefile;
+pub use expandsize::Expandsize;
+pub use failmode::Failmode;
+pub use health::Health;
+pub use onoff::OnOff;
+pub use version::Version;
+pub use yesno::YesNo;
 
-use super::InvalidProperty;
+use libzfs::zpool_prop_t::*;
+
+use super::*;
 
 mod allocated;
 mod altroot;
@@ -13,3 +25,89 @@ mod health;
 mod onoff;
 mod version;
 mod yesno;
+
+#[derive(Debug)]
+pub struct Properties {
+    props: nvpair::NvList,
+}
+
+impl Properties {
+    pub fn new() -> Self {
+        Self {
+            props: nvpair::NvList::new(),
+        }
+    }
+
+    pub fn altroot(&mut self, altroot: impl AsRef<str>) -> &mut Self {
+        self.set(ZPOOL_PROP_ALTROOT, altroot.as_ref())
+    }
+
+    pub fn ashift(&mut self, ashift: u64) -> &mut Self {
+        self.set(ZPOOL_PROP_ASHIFT, ashift)
+    }
+
+    pub fn autoexpand(&mut self, autoexpand: impl Into<OnOff>) -> &mut Self {
+        self.set(ZPOOL_PROP_AUTOEXPAND, autoexpand.into())
+    }
+
+    pub fn autoreplace(&mut self, autoreplace: impl Into<OnOff>) -> &mut Self {
+        self.set(ZPOOL_PROP_AUTOREPLACE, autoreplace.into())
+    }
+
+    pub fn autotrim(&mut self, autotrim: impl Into<OnOff>) -> &mut Self {
+        self.set(ZPOOL_PROP_AUTOTRIM, autotrim.into())
+    }
+
+    pub fn cachefile(&mut self, cachefile: impl AsRef<str>) -> &mut Self {
+        self.set(ZPOOL_PROP_CACHEFILE, cachefile.as_ref())
+    }
+
+    pub fn comment(&mut self, comment: impl AsRef<str>) -> &mut Self {
+        self.set(ZPOOL_PROP_COMMENT, comment.as_ref())
+    }
+
+    pub fn delegation(&mut self, delegation: impl Into<OnOff>) -> &mut Self {
+        self.set(ZPOOL_PROP_DELEGATION, delegation.into())
+    }
+
+    pub fn failmode(&mut self, failmode: impl Into<Failmode>) -> &mut Self {
+        self.set(ZPOOL_PROP_FAILUREMODE, failmode.into())
+    }
+
+    pub fn listsnapshots(&mut self, listsnapshots: impl Into<OnOff>) -> &mut Self {
+        self.set(ZPOOL_PROP_LISTSNAPS, listsnapshots.into())
+    }
+
+    pub fn multihost(&mut self, multihost: impl Into<OnOff>) -> &mut Self {
+        self.set(ZPOOL_PROP_MULTIHOST, multihost.into())
+    }
+
+    /// Import-time only property
+    pub fn readonly(&mut self, readonly: impl Into<OnOff>) -> &mut Self {
+        self.set(ZPOOL_PROP_READONLY, readonly.into())
+    }
+
+    /// Any pool property, including `feature@...`, in its string form
+    pub fn string_property(&mut self, name: impl AsRef<str>, value: impl AsRef<str>) -> &mut Self {
+        self.props += (name.as_ref(), value.as_ref());
+        self
+    }
+
+    // Pool properties are validated by libzfs from their string form
+    fn set(&mut self, property: libzfs::zpool_prop_t, value: impl ToString) -> &mut Self {
+        let name = libzfs::zpool_prop_to_name(property);
+        self.string_property(name, value.to_string())
+    }
+}
+
+impl Default for Properties {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl From<Properties> for nvpair::NvList {
+    fn from(props: Properties) -> Self {
+        props.props
+    }
+}
@@ -7,8 +7,9 @@ use serde::{Deserialize, Serialize};
 #[derive(Debug, Serialize, Deserialize)]
 pub struct Ashift(u64);
 
+// 0 stands for auto-detection
 fn check_range(shift: u64) -> Result<u64, super::InvalidProperty> {
-    if (9..=16).contains(&shift) {
+    if shift == 0 || (9..=16).contains(&shift) {
         Ok(shift)
     } else {
         Err(super::InvalidProperty::invalid_value(shift))
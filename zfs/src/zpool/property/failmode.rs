@@ -3,7 +3,7 @@ use std::str::FromStr;
 
 use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
 pub enum Failmode {
     Wait,
     Continue,
@@ -13,9 +13,9 @@ pub enum Failmode {
 impl Failmode {
     pub fn as_str(&self) -> &str {
         match self {
-            Failmode::Wait => "wait",
-            Failmode::Continue => "continue",
-            Failmode::Panic => "panic",
+            Self::Wait => "wait",
+            Self::Continue => "continue",
+            Self::Panic => "panic",
         }
     }
 }
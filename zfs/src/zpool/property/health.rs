@@ -3,7 +3,7 @@ use std::str::FromStr;
 
 use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "lowercase")]
 pub enum Health {
     Online,
@@ -12,6 +12,8 @@ pub enum Health {
     Offline,
     Removed,
     Unavail,
+    /// The pool I/O is suspended, see `failmode`
+    Suspended,
 }
 
 impl Health {
@@ -23,6 +25,7 @@ impl Health {
             Self::Offline => "offline",
             Self::Removed => "removed",
             Self::Unavail => "unavail",
+            Self::Suspended => "suspended",
         }
     }
 }
@@ -44,6 +47,7 @@ impl FromStr for Health {
             "OFFLINE" => Ok(Self::Offline),
             "REMOVED" => Ok(Self::Removed),
             "UNAVAIL" => Ok(Self::Unavail),
+            "SUSPENDED" => Ok(Self::Suspended),
             other => Err(super::InvalidProperty::invalid_value(other)),
         }
     }
@@ -3,7 +3,7 @@ use std::str::FromStr;
 
 use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
 pub enum OnOff {
     Off,
     On,
@@ -36,6 +36,16 @@ impl FromStr for OnOff {
     }
 }
 
+impl From<bool> for OnOff {
+    fn from(value: bool) -> Self {
+        if value {
+            Self::On
+        } else {
+            Self::Off
+        }
+    }
+}
+
 impl From<OnOff> for bool {
     fn from(onoff: OnOff) -> Self {
         match onoff {
@@ -0,0 +1,207 @@
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use libzfs::dsl_scan_state_t::*;
+use libzfs::pool_scan_func_t::*;
+
+use super::*;
+
+/// Scrub or resilver statistics (pool_scan_stat_t). Times are in seconds since the epoch.
+///
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ScanStats {
+    pub function: ScanFunction,
+    pub state: ScanState,
+    pub start_time: u64,
+    pub end_time: u64,
+    /// Total bytes to scan
+    pub to_examine: u64,
+    /// Bytes located by the scanner
+    pub examined: u64,
+    /// Bytes checked (verified) by the scanner
+    pub issued: u64,
+    /// Bytes the scanner skipped, they are never issued
+    pub skipped: u64,
+    /// Bytes repaired (scrub) or resilvered
+    pub processed: u64,
+    pub errors: u64,
+    /// Examined bytes in the current pass
+    pub pass_examined: u64,
+    /// Issued bytes in the current pass
+    pub pass_issued: u64,
+    pub pass_start: u64,
+    /// When the current scrub pass was paused, 0 if it is running
+    pub pass_scrub_pause: u64,
+    /// Total time the current scrub pass spent paused, in seconds
+    pub pass_scrub_spent_paused: u64,
+}
+
+impl From<libzfs::pool_scan_stat_t> for ScanStats {
+    fn from(stat: libzfs::pool_scan_stat_t) -> Self {
+        Self {
+            function: stat.pss_func.into(),
+            state: stat.pss_state.into(),
+            start_time: stat.pss_start_time,
+            end_time: stat.pss_end_time,
+            to_examine: stat.pss_to_examine,
+            examined: stat.pss_examined,
+            issued: stat.pss_issued,
+            skipped: stat.pss_skipped,
+            processed: stat.pss_processed,
+            errors: stat.pss_errors,
+            pass_examined: stat.pss_pass_exam,
+            pass_issued: stat.pss_pass_issued,
+            pass_start: stat.pss_pass_start,
+            pass_scrub_pause: stat.pss_pass_scrub_pause,
+            pass_scrub_spent_paused: stat.pss_pass_scrub_spent_paused,
+        }
+    }
+}
+
+impl ScanStats {
+    pub fn is_scanning(&self) -> bool {
+        self.state == ScanState::Scanning
+    }
+
+    /// Scrub is paused (resilver cannot be paused)
+    pub fn is_paused(&self) -> bool {
+        self.is_scanning() && self.pass_scrub_pause != 0
+    }
+
+    /// Bytes to be issued: the total without the skipped ones
+    pub fn to_issue(&self) -> u64 {
+        self.to_examine.saturating_sub(self.skipped)
+    }
+
+    /// Percentage of the issued (verified) bytes, as printed by `zpool status`
+    pub fn percent_done(&self) -> f64 {
+        let to_issue = self.to_issue();
+        if to_issue == 0 {
+            return 100.0;
+        }
+        (self.issued as f64 * 100.0 / to_issue as f64).min(100.0)
+    }
+
+    /// Time spent in the current pass, not counting the time it was paused
+    pub fn pass_elapsed(&self) -> Duration {
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |now| now.as_secs());
+        let paused_at = if self.pass_scrub_pause != 0 {
+            self.pass_scrub_pause
+        } else {
+            now
+        };
+        let elapsed = paused_at
+            .saturating_sub(self.pass_start)
+            .saturating_sub(self.pass_scrub_spent_paused);
+        Duration::from_secs(elapsed.max(1))
+    }
+
+    /// Rate (bytes per second) at which the blocks are located
+    pub fn scan_rate(&self) -> u64 {
+        self.pass_examined / self.pass_elapsed().as_secs()
+    }
+
+    /// Rate (bytes per second) at which the blocks are verified
+    pub fn issue_rate(&self) -> u64 {
+        self.pass_issued / self.pass_elapsed().as_secs()
+    }
+
+    /// Estimated time to go, `None` if the scan is not running or the rate is not known yet
+    pub fn time_remaining(&self) -> Option<Duration> {
+        let rate = self.issue_rate();
+        if !self.is_scanning() || self.is_paused() || rate == 0 {
+            return None;
+        }
+        let remaining = self.to_issue().checked_sub(self.issued)?;
+        Some(Duration::from_secs(remaining / rate))
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ScanFunction {
+    None,
+    Scrub,
+    Resilver,
+    ErrorScrub,
+    Unknown,
+}
+
+impl From<u64> for ScanFunction {
+    fn from(function: u64) -> Self {
+        match function {
+            x if x == POOL_SCAN_NONE as u64 => Self::None,
+            x if x == POOL_SCAN_SCRUB as u64 => Self::Scrub,
+            x if x == POOL_SCAN_RESILVER as u64 => Self::Resilver,
+            x if x == POOL_SCAN_ERRORSCRUB as u64 => Self::ErrorScrub,
+            _ => Self::Unknown,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ScanState {
+    None,
+    Scanning,
+    Finished,
+    Canceled,
+    Unknown,
+}
+
+impl From<u64> for ScanState {
+    fn from(state: u64) -> Self {
+        match state {
+            x if x == DSS_NONE as u64 => Self::None,
+            x if x == DSS_SCANNING as u64 => Self::Scanning,
+            x if x == DSS_FINISHED as u64 => Self::Finished,
+            x if x == DSS_CANCELED as u64 => Self::Canceled,
+            _ => Self::Unknown,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn stats() -> ScanStats {
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_secs();
+        ScanStats {
+            function: ScanFunction::Resilver,
+            state: ScanState::Scanning,
+            start_time: now,
+            end_time: 0,
+            to_examine: 1000,
+            examined: 1000,
+            issued: 300,
+            skipped: 600,
+            processed: 300,
+            errors: 0,
+            pass_examined: 1000,
+            pass_issued: 100,
+            pass_start: now,
+            pass_scrub_pause: 0,
+            pass_scrub_spent_paused: 0,
+        }
+    }
+
+    #[test]
+    fn skipped_bytes() {
+        let stats = stats();
+        assert_eq!(stats.to_issue(), 400);
+        assert_eq!(stats.percent_done(), 75.0);
+        // 100 bytes left at 50 to 100 bytes per second, depending on the clock tick
+        let remaining = stats.time_remaining().unwrap();
+        assert!(remaining <= Duration::from_secs(2), "{remaining:?}");
+
+        let stats = ScanStats {
+            skipped: 1000,
+            ..stats
+        };
+        assert_eq!(stats.percent_done(), 100.0);
+        assert_eq!(stats.time_remaining(), None);
+    }
+}
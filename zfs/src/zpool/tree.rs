@@ -0,0 +1,307 @@
+use libzfs::vdev_aux_t::*;
+use libzfs::vdev_state_t::*;
+use libzfs::zpool_config::*;
+
+use super::*;
+
+// vs_ops and vs_bytes are indexed by zio type
+const ZIO_TYPE_READ: usize = 1;
+const ZIO_TYPE_WRITE: usize = 2;
+const ZIO_TYPE_FREE: usize = 3;
+const ZIO_TYPE_CLAIM: usize = 4;
+const ZIO_TYPE_IOCTL: usize = 5;
+
+/// Vdev tree of the pool configuration (ZPOOL_CONFIG_VDEV_TREE)
+///
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct VdevTree {
+    root: Vdev,
+    scan: Option<ScanStats>,
+}
+
+impl VdevTree {
+    pub(crate) fn from_nvlist<T>(nvl: &nvpair::NvListRef<'_, T>) -> Result<Self> {
+        let root = Vdev::from_nvlist(nvl)?;
+        let scan = nvl
+            .lookup_nvpair(ZPOOL_CONFIG_SCAN_STATS)?
+            .map(|stats| libzfs::pool_scan_stat(stats.uint64_array()).into());
+        Ok(Self { root, scan })
+    }
+
+    pub fn root(&self) -> &Vdev {
+        &self.root
+    }
+
+    /// Statistics of the last (or current) scrub or resilver, `None` if there was none
+    pub fn scan(&self) -> Option<&ScanStats> {
+        self.scan.as_ref()
+    }
+
+    /// All the vdevs, depth first, starting with the root
+    pub fn iter(&self) -> impl Iterator<Item = &Vdev> {
+        self.root.iter()
+    }
+
+    /// Leaf vdevs, i.e. the actual devices
+    pub fn leaves(&self) -> impl Iterator<Item = &Vdev> {
+        self.iter().filter(|vdev| vdev.children.is_empty())
+    }
+
+    /// Vdev by guid
+    pub fn find(&self, guid: u64) -> Option<&Vdev> {
+        self.iter().find(|vdev| vdev.guid == guid)
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Vdev {
+    /// Vdev type - root, mirror, raidz, draid, disk, file, etc.
+    pub kind: String,
+    pub path: Option<String>,
+    pub guid: u64,
+    /// Parity of raidz and draid vdevs, 0 for the others
+    pub parity: u64,
+    /// Separate intent log vdev
+    pub log: bool,
+    pub state: VdevState,
+    /// Reason of the vdev state, if it is not healthy
+    pub aux: VdevAux,
+    pub children: Vec<Vdev>,
+    pub stats: Option<VdevStats>,
+}
+
+impl Vdev {
+    fn from_nvlist<T>(nvl: &nvpair::NvListRef<'_, T>) -> Result<Self> {
+        let kind = nvl
+            .lookup_nvpair(ZPOOL_CONFIG_TYPE)?
+            .ok_or_else(DatasetError::missing_value)?
+            .string()
+            .into_owned();
+        let path = nvl
+            .lookup_nvpair(ZPOOL_CONFIG_PATH)?
+            .map(|path| path.string().into_owned());
+        let guid = nvl
+            .lookup_nvpair(ZPOOL_CONFIG_GUID)?
+            .map_or(0, |guid| guid.uint64());
+        let parity = nvl
+            .lookup_nvpair(ZPOOL_CONFIG_NPARITY)?
+            .map_or(0, |parity| parity.uint64());
+        let log = nvl
+            .lookup_nvpair(ZPOOL_CONFIG_IS_LOG)?
+            .map_or(false, |log| log.uint64() != 0);
+        let stats = nvl
+            .lookup_nvpair(ZPOOL_CONFIG_VDEV_STATS)?
+            .map(|stats| libzfs::vdev_stat(stats.uint64_array()));
+        let (state, aux) = stats.map_or((VdevState::Unknown, VdevAux::None), |stats| {
+            (stats.vs_state.into(), stats.vs_aux.into())
+        });
+        let children = match nvl.lookup_nvpair(ZPOOL_CONFIG_CHILDREN)? {
+            Some(children) => children
+                .nvlist_array()
+                .iter()
+                .map(Self::from_nvlist)
+                .collect::<Result<_>>()?,
+            None => Vec::new(),
+        };
+
+        Ok(Self {
+            kind,
+            path,
+            guid,
+            parity,
+            log,
+            state,
+            aux,
+            children,
+            stats: stats.map(VdevStats::from),
+        })
+    }
+
+    /// This vdev and all its descendants, depth first
+    pub fn iter(&self) -> impl Iterator<Item = &Self> {
+        let mut stack = vec![self];
+        std::iter::from_fn(move || {
+            let vdev = stack.pop()?;
+            stack.extend(vdev.children.iter().rev());
+            Some(vdev)
+        })
+    }
+
+    /// Number of the child device failures this vdev survives
+    pub fn redundancy(&self) -> u64 {
+        match self.kind.as_str() {
+            "mirror" | "replacing" | "spare" => (self.children.len() as u64).saturating_sub(1),
+            _ => self.parity,
+        }
+    }
+
+    /// Health as reported by `zpool status`
+    pub fn health(&self) -> property::Health {
+        match (self.state, self.aux) {
+            (VdevState::CantOpen, VdevAux::CorruptData | VdevAux::BadLog) => {
+                property::Health::Faulted
+            }
+            (state, _) => state.into(),
+        }
+    }
+}
+
+/// Vdev statistics (vdev_stat_t)
+///
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct VdevStats {
+    /// Allocated bytes
+    pub alloc: u64,
+    /// Total capacity
+    pub space: u64,
+    /// Deflated capacity
+    pub dspace: u64,
+    pub ops: IoStats,
+    pub bytes: IoStats,
+    pub read_errors: u64,
+    pub write_errors: u64,
+    pub checksum_errors: u64,
+    /// Bytes repaired by self healing
+    pub self_healed: u64,
+    /// Bytes scanned by the current scrub or resilver
+    pub scan_processed: u64,
+    /// Free space fragmentation percentage
+    pub fragmentation: u64,
+    /// I/Os slower than zio_slow_io_ms
+    pub slow_ios: u64,
+}
+
+impl VdevStats {
+    pub fn errors(&self) -> u64 {
+        self.read_errors + self.write_errors + self.checksum_errors
+    }
+}
+
+impl From<libzfs::vdev_stat_t> for VdevStats {
+    fn from(stat: libzfs::vdev_stat_t) -> Self {
+        Self {
+            alloc: stat.vs_alloc,
+            space: stat.vs_space,
+            dspace: stat.vs_dspace,
+            ops: IoStats::from(&stat.vs_ops),
+            bytes: IoStats::from(&stat.vs_bytes),
+            read_errors: stat.vs_read_errors,
+            write_errors: stat.vs_write_errors,
+            checksum_errors: stat.vs_checksum_errors,
+            self_healed: stat.vs_self_healed,
+            scan_processed: stat.vs_scan_processed,
+            fragmentation: stat.vs_fragmentation,
+            slow_ios: stat.vs_slow_ios,
+        }
+    }
+}
+
+/// Per I/O type counters, either operations or bytes
+///
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct IoStats {
+    pub read: u64,
+    pub write: u64,
+    pub free: u64,
+    pub claim: u64,
+    pub ioctl: u64,
+}
+
+impl From<&[u64; 6]> for IoStats {
+    fn from(stats: &[u64; 6]) -> Self {
+        Self {
+            read: stats[ZIO_TYPE_READ],
+            write: stats[ZIO_TYPE_WRITE],
+            free: stats[ZIO_TYPE_FREE],
+            claim: stats[ZIO_TYPE_CLAIM],
+            ioctl: stats[ZIO_TYPE_IOCTL],
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum VdevState {
+    Unknown,
+    Closed,
+    Offline,
+    Removed,
+    CantOpen,
+    Faulted,
+    Degraded,
+    Healthy,
+}
+
+impl From<u64> for VdevState {
+    fn from(state: u64) -> Self {
+        match state {
+            x if x == VDEV_STATE_CLOSED as u64 => Self::Closed,
+            x if x == VDEV_STATE_OFFLINE as u64 => Self::Offline,
+            x if x == VDEV_STATE_REMOVED as u64 => Self::Removed,
+            x if x == VDEV_STATE_CANT_OPEN as u64 => Self::CantOpen,
+            x if x == VDEV_STATE_FAULTED as u64 => Self::Faulted,
+            x if x == VDEV_STATE_DEGRADED as u64 => Self::Degraded,
+            x if x == VDEV_STATE_HEALTHY as u64 => Self::Healthy,
+            _ => Self::Unknown,
+        }
+    }
+}
+
+impl From<VdevState> for property::Health {
+    fn from(state: VdevState) -> Self {
+        match state {
+            VdevState::Healthy => Self::Online,
+            VdevState::Degraded => Self::Degraded,
+            VdevState::Faulted => Self::Faulted,
+            VdevState::Closed | VdevState::Offline => Self::Offline,
+            VdevState::Removed => Self::Removed,
+            VdevState::CantOpen | VdevState::Unknown => Self::Unavail,
+        }
+    }
+}
+
+/// Reason of the vdev state (vdev_aux_t)
+///
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum VdevAux {
+    None,
+    OpenFailed,
+    CorruptData,
+    NoReplicas,
+    BadGuidSum,
+    TooSmall,
+    BadLabel,
+    VersionNewer,
+    VersionOlder,
+    UnsupportedFeature,
+    Spared,
+    ErrorsExceeded,
+    IoFailure,
+    BadLog,
+    External,
+    SplitPool,
+    Other(u64),
+}
+
+impl From<u64> for VdevAux {
+    fn from(aux: u64) -> Self {
+        match aux {
+            x if x == VDEV_AUX_NONE as u64 => Self::None,
+            x if x == VDEV_AUX_OPEN_FAILED as u64 => Self::OpenFailed,
+            x if x == VDEV_AUX_CORRUPT_DATA as u64 => Self::CorruptData,
+            x if x == VDEV_AUX_NO_REPLICAS as u64 => Self::NoReplicas,
+            x if x == VDEV_AUX_BAD_GUID_SUM as u64 => Self::BadGuidSum,
+            x if x == VDEV_AUX_TOO_SMALL as u64 => Self::TooSmall,
+            x if x == VDEV_AUX_BAD_LABEL as u64 => Self::BadLabel,
+            x if x == VDEV_AUX_VERSION_NEWER as u64 => Self::VersionNewer,
+            x if x == VDEV_AUX_VERSION_OLDER as u64 => Self::VersionOlder,
+            x if x == VDEV_AUX_UNSUP_FEAT as u64 => Self::UnsupportedFeature,
+            x if x == VDEV_AUX_SPARED as u64 => Self::Spared,
+            x if x == VDEV_AUX_ERR_EXCEEDED as u64 => Self::ErrorsExceeded,
+            x if x == VDEV_AUX_IO_FAILURE as u64 => Self::IoFailure,
+            x if x == VDEV_AUX_BAD_LOG as u64 => Self::BadLog,
+            x if x == VDEV_AUX_EXTERNAL as u64 => Self::External,
+            x if x == VDEV_AUX_SPLIT_POOL as u64 => Self::SplitPool,
+            other => Self::Other(other),
+        }
+    }
+}
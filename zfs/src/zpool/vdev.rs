@@ -0,0 +1,422 @@
+use std::fs;
+use std::os::unix::fs::FileTypeExt;
+use std::path::{Path, PathBuf};
+
+use libzfs::zpool_config::*;
+
+use super::*;
+
+const TYPE_ROOT: &str = "root";
+const TYPE_MIRROR: &str = "mirror";
+const TYPE_RAIDZ: &str = "raidz";
+const TYPE_DRAID: &str = "draid";
+const TYPE_DISK: &str = "disk";
+const TYPE_FILE: &str = "file";
+
+const DRAID_MAX_DATA: u64 = 8;
+
+/// Single top-level vdev. Devices are given by absolute paths, either to block devices
+/// or to regular files (at least 64M each).
+///
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Vdev {
+    Device(PathBuf),
+    Mirror(Vec<PathBuf>),
+    Raidz {
+        parity: u64,
+        devices: Vec<PathBuf>,
+    },
+    Draid {
+        parity: u64,
+        data: Option<u64>,
+        spares: u64,
+        devices: Vec<PathBuf>,
+    },
+}
+
+impl Vdev {
+    pub fn device(path: impl AsRef<Path>) -> Self {
+        Self::Device(path.as_ref().to_path_buf())
+    }
+
+    pub fn mirror<P: AsRef<Path>>(devices: impl IntoIterator<Item = P>) -> Self {
+        Self::Mirror(paths(devices))
+    }
+
+    pub fn raidz1<P: AsRef<Path>>(devices: impl IntoIterator<Item = P>) -> Self {
+        Self::raidz(1, devices)
+    }
+
+    pub fn raidz2<P: AsRef<Path>>(devices: impl IntoIterator<Item = P>) -> Self {
+        Self::raidz(2, devices)
+    }
+
+    pub fn raidz3<P: AsRef<Path>>(devices: impl IntoIterator<Item = P>) -> Self {
+        Self::raidz(3, devices)
+    }
+
+    /// dRAID vdev with `parity` (1-3) and distributed `spares`. The number of data devices
+    /// per redundancy group is derived from the number of children, as `zpool` does.
+    ///
+    pub fn draid<P: AsRef<Path>>(
+        parity: u64,
+        spares: u64,
+        devices: impl IntoIterator<Item = P>,
+    ) -> Self {
+        Self::Draid {
+            parity,
+            data: None,
+            spares,
+            devices: paths(devices),
+        }
+    }
+
+    /// Set the number of data devices per dRAID redundancy group. No-op for other vdev types.
+    ///
+    pub fn data(self, ndata: u64) -> Self {
+        match self {
+            Self::Draid {
+                parity,
+                spares,
+                devices,
+                ..
+            } => Self::Draid {
+                parity,
+                data: Some(ndata),
+                spares,
+                devices,
+            },
+            other => other,
+        }
+    }
+
+    fn raidz<P: AsRef<Path>>(parity: u64, devices: impl IntoIterator<Item = P>) -> Self {
+        Self::Raidz {
+            parity,
+            devices: paths(devices),
+        }
+    }
+
+    /// Number of device failures this vdev survives
+    pub(crate) fn redundancy(&self) -> u64 {
+        match self {
+            Self::Device(_) => 0,
+            Self::Mirror(devices) => (devices.len() as u64).saturating_sub(1),
+            Self::Raidz { parity, .. } | Self::Draid { parity, .. } => *parity,
+        }
+    }
+
+    fn devices(&self) -> &[PathBuf] {
+        match self {
+            Self::Device(device) => std::slice::from_ref(device),
+            Self::Mirror(devices) | Self::Raidz { devices, .. } | Self::Draid { devices, .. } => {
+                devices
+            }
+        }
+    }
+
+    fn to_nvlist(&self, class: Class) -> Result<nvpair::NvList> {
+        if class == Class::Log && matches!(self, Self::Raidz { .. } | Self::Draid { .. }) {
+            return Err(DatasetError::invalid_vdev_spec(
+                "log vdevs must be single devices or mirrors",
+            ));
+        }
+
+        let mut nvl = match self {
+            Self::Device(device) => leaf(device)?,
+            Self::Mirror(devices) => {
+                if devices.len() < 2 {
+                    return Err(DatasetError::invalid_vdev_spec(
+                        "mirror requires at least 2 devices",
+                    ));
+                }
+                interior(TYPE_MIRROR, devices)?
+            }
+            Self::Raidz { parity, devices } => {
+                if !(1..=3).contains(parity) {
+                    return Err(DatasetError::invalid_vdev_spec(format!(
+                        "invalid raidz parity {parity}"
+                    )));
+                }
+                if (devices.len() as u64) <= *parity {
+                    return Err(DatasetError::invalid_vdev_spec(format!(
+                        "raidz{parity} requires at least {} devices",
+                        parity + 1
+                    )));
+                }
+                let mut nvl = interior(TYPE_RAIDZ, devices)?;
+                nvl.add_uint64(ZPOOL_CONFIG_NPARITY, *parity)?;
+                nvl
+            }
+            Self::Draid {
+                parity,
+                data,
+                spares,
+                devices,
+            } => {
+                let (ndata, ngroups) = draid_geometry(*parity, *data, *spares, devices.len())?;
+                let mut nvl = interior(TYPE_DRAID, devices)?;
+                nvl.add_uint64(ZPOOL_CONFIG_NPARITY, *parity)?;
+                nvl.add_uint64(ZPOOL_CONFIG_DRAID_NDATA, ndata)?;
+                nvl.add_uint64(ZPOOL_CONFIG_DRAID_NSPARES, *spares)?;
+                nvl.add_uint64(ZPOOL_CONFIG_DRAID_NGROUPS, ngroups)?;
+                nvl
+            }
+        };
+
+        nvl.add_uint64(ZPOOL_CONFIG_IS_LOG, u64::from(class == Class::Log))?;
+        if let Some(bias) = class.bias() {
+            nvl.add_string(ZPOOL_CONFIG_ALLOCATION_BIAS, bias)?;
+        }
+
+        Ok(nvl)
+    }
+}
+
+/// Pool vdev topology - data vdevs along with the special, dedup and log allocation classes,
+/// L2ARC cache devices and hot spares.
+///
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct VdevSpec {
+    data: Vec<Vdev>,
+    special: Vec<Vdev>,
+    dedup: Vec<Vdev>,
+    log: Vec<Vdev>,
+    cache: Vec<PathBuf>,
+    spares: Vec<PathBuf>,
+}
+
+impl VdevSpec {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add data vdev
+    pub fn vdev(mut self, vdev: Vdev) -> Self {
+        self.data.push(vdev);
+        self
+    }
+
+    /// Add single device data vdev (stripe)
+    pub fn device(self, path: impl AsRef<Path>) -> Self {
+        self.vdev(Vdev::device(path))
+    }
+
+    pub fn mirror<P: AsRef<Path>>(self, devices: impl IntoIterator<Item = P>) -> Self {
+        self.vdev(Vdev::mirror(devices))
+    }
+
+    pub fn raidz1<P: AsRef<Path>>(self, devices: impl IntoIterator<Item = P>) -> Self {
+        self.vdev(Vdev::raidz1(devices))
+    }
+
+    pub fn raidz2<P: AsRef<Path>>(self, devices: impl IntoIterator<Item = P>) -> Self {
+        self.vdev(Vdev::raidz2(devices))
+    }
+
+    pub fn raidz3<P: AsRef<Path>>(self, devices: impl IntoIterator<Item = P>) -> Self {
+        self.vdev(Vdev::raidz3(devices))
+    }
+
+    pub fn draid<P: AsRef<Path>>(
+        self,
+        parity: u64,
+        spares: u64,
+        devices: impl IntoIterator<Item = P>,
+    ) -> Self {
+        self.vdev(Vdev::draid(parity, spares, devices))
+    }
+
+    /// Add special allocation class vdev (metadata and small blocks)
+    pub fn special(mut self, vdev: Vdev) -> Self {
+        self.special.push(vdev);
+        self
+    }
+
+    /// Add dedup allocation class vdev (dedup tables)
+    pub fn dedup(mut self, vdev: Vdev) -> Self {
+        self.dedup.push(vdev);
+        self
+    }
+
+    /// Add separate intent log (SLOG) vdev
+    pub fn log(mut self, vdev: Vdev) -> Self {
+        self.log.push(vdev);
+        self
+    }
+
+    /// Add L2ARC cache device
+    pub fn cache(mut self, path: impl AsRef<Path>) -> Self {
+        self.cache.push(path.as_ref().to_path_buf());
+        self
+    }
+
+    /// Add hot spare device
+    pub fn spare(mut self, path: impl AsRef<Path>) -> Self {
+        self.spares.push(path.as_ref().to_path_buf());
+        self
+    }
+
+    /// All the devices used by this spec
+    pub fn devices(&self) -> impl Iterator<Item = &Path> {
+        self.data
+            .iter()
+            .chain(&self.special)
+            .chain(&self.dedup)
+            .chain(&self.log)
+            .flat_map(Vdev::devices)
+            .chain(&self.cache)
+            .chain(&self.spares)
+            .map(PathBuf::as_path)
+    }
+
+    /// Root vdev nvlist (nvroot) as expected by `zpool_create()`
+    pub fn to_nvlist(&self) -> Result<nvpair::NvList> {
+        if self.data.is_empty() {
+            return Err(DatasetError::invalid_vdev_spec("no data vdevs"));
+        }
+        self.nvroot()
+    }
+
+    /// Data, special and dedup vdevs, i.e. the ones holding the pool data
+    pub(crate) fn data_vdevs(&self) -> impl Iterator<Item = &Vdev> {
+        self.data.iter().chain(&self.special).chain(&self.dedup)
+    }
+
+    /// Root vdev nvlist (nvroot) as expected by `zpool_add()`, any vdev class will do
+    pub(crate) fn nvroot(&self) -> Result<nvpair::NvList> {
+        if self.devices().next().is_none() {
+            return Err(DatasetError::invalid_vdev_spec("no vdevs"));
+        }
+
+        let classes = [
+            (&self.data, Class::Data),
+            (&self.special, Class::Special),
+            (&self.dedup, Class::Dedup),
+            (&self.log, Class::Log),
+        ];
+        let children = classes
+            .into_iter()
+            .flat_map(|(vdevs, class)| vdevs.iter().map(move |vdev| vdev.to_nvlist(class)))
+            .collect::<Result<Vec<_>>>()?;
+
+        let mut nvroot = nvpair::NvList::new();
+        nvroot.add_string(ZPOOL_CONFIG_TYPE, TYPE_ROOT)?;
+        nvroot.add_nvlist_array(ZPOOL_CONFIG_CHILDREN, &children)?;
+        if !self.spares.is_empty() {
+            let spares = self
+                .spares
+                .iter()
+                .map(|device| leaf(device))
+                .collect::<Result<Vec<_>>>()?;
+            nvroot.add_nvlist_array(ZPOOL_CONFIG_SPARES, &spares)?;
+        }
+        if !self.cache.is_empty() {
+            let cache = self
+                .cache
+                .iter()
+                .map(|device| leaf(device))
+                .collect::<Result<Vec<_>>>()?;
+            nvroot.add_nvlist_array(ZPOOL_CONFIG_L2CACHE, &cache)?;
+        }
+
+        Ok(nvroot)
+    }
+}
+
+impl From<Vdev> for VdevSpec {
+    fn from(vdev: Vdev) -> Self {
+        Self::new().vdev(vdev)
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Class {
+    Data,
+    Special,
+    Dedup,
+    Log,
+}
+
+impl Class {
+    fn bias(self) -> Option<&'static str> {
+        match self {
+            Self::Data => None,
+            Self::Special => Some("special"),
+            Self::Dedup => Some("dedup"),
+            Self::Log => Some("log"),
+        }
+    }
+}
+
+fn paths<P: AsRef<Path>>(devices: impl IntoIterator<Item = P>) -> Vec<PathBuf> {
+    devices
+        .into_iter()
+        .map(|device| device.as_ref().to_path_buf())
+        .collect()
+}
+
+fn leaf(path: &Path) -> Result<nvpair::NvList> {
+    if !path.is_absolute() {
+        return Err(DatasetError::invalid_vdev_spec(format!(
+            "device path must be absolute ({})",
+            path.display()
+        )));
+    }
+    let r#type = if fs::metadata(path)?.file_type().is_block_device() {
+        TYPE_DISK
+    } else {
+        TYPE_FILE
+    };
+    let path = path
+        .to_str()
+        .ok_or_else(|| DatasetError::invalid_vdev_spec(path.display().to_string()))?;
+
+    let mut nvl = nvpair::NvList::new();
+    nvl.add_string(ZPOOL_CONFIG_TYPE, r#type)?;
+    nvl.add_string(ZPOOL_CONFIG_PATH, path)?;
+    // Devices are used as is, without being partitioned
+    nvl.add_uint64(ZPOOL_CONFIG_WHOLE_DISK, 0)?;
+    Ok(nvl)
+}
+
+fn interior(r#type: &str, devices: &[PathBuf]) -> Result<nvpair::NvList> {
+    let children = devices
+        .iter()
+        .map(|device| leaf(device))
+        .collect::<Result<Vec<_>>>()?;
+    let mut nvl = nvpair::NvList::new();
+    nvl.add_string(ZPOOL_CONFIG_TYPE, r#type)?;
+    nvl.add_nvlist_array(ZPOOL_CONFIG_CHILDREN, &children)?;
+    Ok(nvl)
+}
+
+/// Number of data devices and redundancy groups of dRAID vdev, following `zpool create`
+fn draid_geometry(
+    parity: u64,
+    data: Option<u64>,
+    spares: u64,
+    children: usize,
+) -> Result<(u64, u64)> {
+    let children = children as u64;
+    if !(1..=3).contains(&parity) {
+        return Err(DatasetError::invalid_vdev_spec(format!(
+            "invalid draid parity {parity}"
+        )));
+    }
+    let available = children.saturating_sub(parity + spares);
+    let ndata = data.unwrap_or_else(|| available.min(DRAID_MAX_DATA));
+    if ndata == 0 || ndata > available {
+        return Err(DatasetError::invalid_vdev_spec(format!(
+            "draid{parity} with {spares} spares and {ndata} data devices \
+             does not fit {children} children"
+        )));
+    }
+
+    let group = ndata + parity;
+    let width = children - spares;
+    let ngroups = (1..)
+        .find(|ngroups| (ngroups * group) % width == 0)
+        .unwrap_or(1);
+    Ok((ndata, ngroups))
+}
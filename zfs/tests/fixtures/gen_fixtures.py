@@ -0,0 +1,171 @@
+#!/usr/bin/env python3
+"""Generate the synthetic send stream fixtures used by zfs/tests/stream.rs.
+
+Usage: python3 gen_fixtures.py zfs/tests/fixtures
+
+See README.md in this directory for how the fixtures relate to real
+`zfs send` output.
+"""
+
+import struct
+import sys
+MAGIC = 0x2F5bacbac
+RS = 312
+M64 = (1 << 64) - 1
+
+class Stream:
+    def __init__(self, e):
+        self.e = e
+        self.out = bytearray()
+        self.ck = [0, 0, 0, 0]
+    def fl(self, data):
+        a, b, c, d = self.ck
+        for (w,) in struct.iter_unpack(self.e + 'I', bytes(data)):
+            a = (a + w) & M64; b = (b + a) & M64; c = (c + b) & M64; d = (d + c) & M64
+        self.ck = [a, b, c, d]
+    def rec(self, typ, union, payload=b'', payloadlen=0):
+        hdr = bytearray(RS)
+        struct.pack_into(self.e + 'II', hdr, 0, typ, payloadlen)
+        hdr[8:8 + len(union)] = union
+        prev = list(self.ck)
+        if typ == 5:  # END carries the checksum of everything before
+            struct.pack_into(self.e + '4Q', hdr, 8, *prev)
+        self.fl(hdr[:280])
+        if typ != 0:
+            struct.pack_into(self.e + '4Q', hdr, 280, *self.ck)
+        self.fl(hdr[280:])
+        self.fl(payload)
+        self.out += hdr + payload
+        if typ == 5:
+            self.ck = [0, 0, 0, 0]
+    def p(self, fmt, *a):
+        return struct.pack(self.e + fmt, *a)
+
+    def begin(self, hdrtype, features, toname, toguid, fromguid=0, payload=b'', flags=0):
+        vi = hdrtype | (features << 2)
+        u = self.p('QQQIIQQ', MAGIC, vi, 1650000000, 2, flags, toguid, fromguid)
+        u += toname.encode().ljust(256, b'\0')
+        self.rec(0, u, payload, len(payload))
+    def end(self, toguid, zero=False):
+        if zero:
+            hdr = bytearray(RS)  # final END of compound stream, all zeros
+            struct.pack_into(self.e + 'II', hdr, 0, 5, 0)
+            self.fl(hdr); self.out += hdr; self.ck = [0, 0, 0, 0]
+            return
+        self.rec(5, b'\0' * 32 + self.p('Q', toguid))
+
+TOGUID = 0x5d1c2a6f9e03b471
+
+# DMU_BACKUP_FEATURE_* flags
+DEDUP = 1 << 0
+DEDUPPROPS = 1 << 1
+SA_SPILL = 1 << 2
+EMBED_DATA = 1 << 16
+LZ4 = 1 << 17
+LARGE_BLOCKS = 1 << 19
+REDACTED = 1 << 21
+COMPRESSED = 1 << 22
+RAW = 1 << 24
+
+# zfs send -L -e -c of a dataset with spill blocks
+FEAT = SA_SPILL | EMBED_DATA | LZ4 | LARGE_BLOCKS | COMPRESSED
+
+def full(e):
+    s = Stream(e)
+    s.begin(1, FEAT, "rpool/test@snap", TOGUID)
+    s.rec(2, s.p('QQQ', 0, 1, TOGUID))  # FREEOBJECTS
+    bonus = bytes(range(1, 169))  # 168 bytes
+    s.rec(1, s.p('QIIIIBBBBIQBBB5xQ', 1, 19, 44, 131072, 168, 7, 15, 1, 0, 0, TOGUID, 0, 0, 0, 0), bonus)  # OBJECT
+    data = bytes((i * 7) & 0xff for i in range(4096))
+    s.rec(3, s.p('QIIQQQBBB5x', 1, 19, 0, 0, 4096, TOGUID, 7, 0, 0) + b'\0' * 40 + s.p('Q', 0) + b'\0' * 36, data)  # WRITE
+    comp = bytes((i * 13) & 0xff for i in range(1024))
+    s.rec(3, s.p('QIIQQQBBB5x', 1, 19, 0, 131072, 131072, TOGUID, 7, 0, 15) + b'\0' * 40 + s.p('Q', 1024) + b'\0' * 36, comp)  # compressed WRITE
+    s.rec(4, s.p('QQQQ', 1, 262144, M64, TOGUID))  # FREE
+    s.rec(8, s.p('QQQQBB6xII', 2, 0, 512, TOGUID, 15, 0, 512, 20), bytes(range(20)) + b'\0' * 4)  # WRITE_EMBEDDED
+    s.rec(7, s.p('QQQBB6xQ', 1, 512, TOGUID, 0, 0, 0) + b'\0' * 36 + s.p('I', 45), bytes(512))  # SPILL
+    s.rec(2, s.p('QQQ', 5, (1 << 64) - 5, TOGUID))  # FREEOBJECTS
+    s.end(TOGUID)
+    return s.out
+
+def object_and_write(s, size=4096):
+    s.rec(1, s.p('QIIIIBBBBIQBBB5xQ', 1, 19, 44, 131072, 168, 7, 15, 1, 0, 0, TOGUID, 0, 0, 0, 0), bytes(168))  # OBJECT
+    s.rec(3, s.p('QIIQQQBBB5x', 1, 19, 0, 0, size, TOGUID, 7, 0, 0) + b'\0' * 40 + s.p('Q', 0) + b'\0' * 36, bytes(size))  # WRITE
+
+def raw(e):
+    # OBJECT_RANGE records are only sent with -w
+    s = Stream(e)
+    s.begin(1, FEAT | RAW, "rpool/test@snap", TOGUID)
+    s.rec(9, s.p('QQQ', 0, 32, TOGUID) + b'\0' * 36 + b'\0' * 4)  # OBJECT_RANGE
+    object_and_write(s)
+    s.end(TOGUID)
+    return s.out
+
+def redacted(e):
+    # REDACT records are only sent with --redact
+    s = Stream(e)
+    s.begin(1, FEAT | REDACTED, "rpool/test@snap", TOGUID)
+    object_and_write(s)
+    s.rec(10, s.p('QQQQ', 1, 131072, 131072, TOGUID))  # REDACT
+    s.end(TOGUID)
+    return s.out
+
+def dedup(e):
+    # WRITE_BYREF records are only found in deduplicated streams (zstream dedup)
+    s = Stream(e)
+    s.begin(1, FEAT | DEDUP | DEDUPPROPS, "rpool/test@snap", TOGUID)
+    object_and_write(s)
+    s.rec(6, s.p('QQQQQQQBB6x', 1, 4096, 4096, TOGUID, TOGUID, 1, 0, 7, 0) + b'\0' * 40)  # WRITE_BYREF
+    s.end(TOGUID)
+    return s.out
+
+# --- XDR packed nvlist ---
+def xdr_str(x):
+    b = x.encode()
+    return struct.pack('>I', len(b)) + b + b'\0' * ((4 - len(b) % 4) % 4)
+def align8(n): return (n + 7) & ~7
+def nvp_native(name, vsz): return align8(16 + len(name) + 1) + align8(vsz)
+def xdr_nvlist_body(pairs):
+    out = struct.pack('>iI', 0, 1)
+    for name, kind, val in pairs:
+        if kind == 'u64':
+            typ, data, vsz = 8, struct.pack('>Q', val), 8
+        elif kind == 'str':
+            typ, data, vsz = 9, xdr_str(val), len(val) + 1
+        elif kind == 'nvl':
+            typ, data, vsz = 19, xdr_nvlist_body(val), 24
+        body = xdr_str(name) + struct.pack('>ii', typ, 1) + data
+        out += struct.pack('>ii', 8 + len(body), nvp_native(name, vsz)) + body
+    return out + struct.pack('>ii', 0, 0)
+def xdr_nvlist(pairs):
+    return bytes([1, 1, 0, 0]) + xdr_nvlist_body(pairs)
+
+def replicate(e):
+    s = Stream(e)
+    nvl = xdr_nvlist([
+        ('tosnap', 'str', 'snap'),
+        ('fss', 'nvl', [
+            ('0x%x' % TOGUID, 'nvl', [
+                ('name', 'str', 'rpool/test'),
+                ('parentfromsnap', 'u64', 0),
+                ('snaps', 'nvl', [('snap', 'u64', TOGUID)]),
+            ]),
+        ]),
+    ])
+    s.begin(2, FEAT, "rpool/test@snap", 0, payload=nvl)
+    s.end(0)
+    s.begin(1, FEAT, "rpool/test@snap", TOGUID)
+    s.rec(2, s.p('QQQ', 0, 1, TOGUID))
+    s.rec(3, s.p('QIIQQQBBB5x', 1, 19, 0, 0, 512, TOGUID, 7, 0, 0) + b'\0' * 88, bytes(512))
+    s.end(TOGUID)
+    s.end(0, zero=True)
+    return s.out
+
+out = sys.argv[1]
+f = full('<'); open(out + '/full.zstream', 'wb').write(f)
+open(out + '/full-be.zstream', 'wb').write(full('>'))
+open(out + '/replicate.zstream', 'wb').write(replicate('<'))
+open(out + '/raw.zstream', 'wb').write(raw('<'))
+open(out + '/redacted.zstream', 'wb').write(redacted('<'))
+open(out + '/dedup.zstream', 'wb').write(dedup('<'))
+bad = bytearray(f); bad[312 * 4 + 168 + 100] ^= 0xff  # flip byte inside the first WRITE payload
+open(out + '/corrupted.zstream', 'wb').write(bad)
@@ -6,7 +6,10 @@ use razor_test::TestNamespace;
 use razor_zfs as zfs;
 
 use zfs::zfs::property;
-// use zfs::Filesystem;
+use zfs::zfs::property::zfs_prop_t::ZFS_PROP_COMPRESSION;
+use zfs::DatasetError;
+use zfs::Filesystem;
+use zfs::MountOptions;
 use zfs::Zfs;
 
 #[test]
@@ -206,7 +209,7 @@ fn filesystem_snapshot() {
 
     for snapshot in snapshots {
         dbg!(snapshot.name());
-        assert!(snapshot.r#type().is_snapshot());
+        assert!(snapshot.is_snapshot());
     }
 }
 
@@ -234,7 +237,7 @@ fn list_filesystems() {
 
     for dataset in datasets {
         dbg!(dataset.name());
-        assert!(dataset.r#type().is_filesystem());
+        assert!(dataset.is_filesystem());
     }
 }
 
@@ -294,7 +297,7 @@ fn list_filesystems() {
 //             names.contains(&dataset.name().to_string()),
 //             "received dataset dont exist in names vector"
 //         );
-//         assert!(dataset.r#type().is_filesystem());
+//         assert!(dataset.is_filesystem());
 //     }
 
 //     dbg!("finished asserting: all good");
@@ -318,7 +321,7 @@ fn list_volumes() {
 
     for dataset in datasets {
         dbg!(dataset.name());
-        assert!(dataset.r#type().is_volume());
+        assert!(dataset.is_volume());
     }
 }
 
@@ -391,3 +394,306 @@ fn delete_non_existent_volume() {
     // ));
     // assert_eq!(expected, res);
 }
+
+#[test]
+fn mount_unmount_filesystem() -> anyhow::Result<()> {
+    let namespace = TestNamespace::unique();
+    let name = namespace.unique_name();
+    let mountpoint = std::env::temp_dir().join(format!("razor-mount-{}", std::process::id()));
+    let filesystem = Zfs::filesystem()
+        .canmount(property::CanMount::On)
+        .mountpoint(mountpoint.to_string_lossy())
+        .create(&name)?;
+    assert!(filesystem.is_mounted());
+    assert_eq!(
+        filesystem.mounted_at().as_deref(),
+        Some(mountpoint.to_string_lossy().as_ref())
+    );
+    filesystem.unmount(false)?;
+    assert!(!filesystem.is_mounted());
+    filesystem.mount(MountOptions::new().readonly())?;
+    assert!(filesystem.is_mounted());
+    filesystem.unmount(false)?;
+    Filesystem::mount_all_under(&name)?;
+    assert!(filesystem.is_mounted());
+    filesystem.destroy()?;
+    std::fs::remove_dir(&mountpoint)?;
+    Ok(())
+}
+
+#[test]
+fn mount_all_in_mountpoint_order() -> anyhow::Result<()> {
+    let namespace = TestNamespace::unique();
+    let name = namespace.unique_name();
+    let base = std::env::temp_dir().join(format!("razor-mount-all-{}", std::process::id()));
+    // The child is mounted above its parent, mounting it last would shadow the parent
+    let mut parent = Zfs::filesystem()
+        .canmount(property::CanMount::NoAuto)
+        .mountpoint(base.join("parent/inner").to_string_lossy())
+        .create(&name)?;
+    let mut child = Zfs::filesystem()
+        .canmount(property::CanMount::NoAuto)
+        .mountpoint(base.join("parent").to_string_lossy())
+        .create(format!("{name}/child"))?;
+    parent.set().canmount(property::CanMount::On).commit()?;
+    child.set().canmount(property::CanMount::On).commit()?;
+
+    Filesystem::mount_all_under(&name)?;
+    assert!(parent.is_mounted());
+    assert!(child.is_mounted());
+    assert!(base.join("parent/inner").is_dir());
+
+    child.destroy()?;
+    parent.destroy()?;
+    std::fs::remove_dir_all(&base)?;
+    Ok(())
+}
+
+#[test]
+fn property_source_and_inherit() -> anyhow::Result<()> {
+    let namespace = TestNamespace::unique();
+    let name = namespace.unique_name();
+    let mut filesystem = Zfs::filesystem()
+        .compression(property::Compression::Gzip)
+        .create(&name)?;
+    let (value, source) = filesystem.get_property_detailed(ZFS_PROP_COMPRESSION)?;
+    assert_eq!(value, "gzip");
+    assert_eq!(source, property::PropSource::Local);
+    filesystem.set().inherit(ZFS_PROP_COMPRESSION).commit()?;
+    let (_, source) = filesystem.get_property_detailed(ZFS_PROP_COMPRESSION)?;
+    assert!(!source.is_local());
+    filesystem.destroy()?;
+    Ok(())
+}
+
+#[test]
+fn user_properties() -> anyhow::Result<()> {
+    let namespace = TestNamespace::unique();
+    let name = namespace.unique_name();
+    let mut filesystem = Zfs::filesystem().create(&name)?;
+    assert!(filesystem.set_user_property("owner", "razor").is_err());
+    filesystem.set_user_property("com.razor:owner", "razor")?;
+    let props = filesystem.user_properties();
+    assert_eq!(
+        props.get("com.razor:owner"),
+        Some(&("razor".to_string(), property::PropSource::Local))
+    );
+    filesystem.inherit_user_property("com.razor:owner")?;
+    assert!(!filesystem.user_properties().contains_key("com.razor:owner"));
+    filesystem.destroy()?;
+    Ok(())
+}
+
+#[test]
+fn open_any_dataset() -> anyhow::Result<()> {
+    let namespace = TestNamespace::unique();
+    let name = namespace.unique_name();
+    let filesystem = Zfs::filesystem().create(&name)?;
+    filesystem.snapshot("snap")?;
+    let snapshot = format!("{name}@snap");
+
+    let dataset = Zfs::open(&name)?;
+    assert!(dataset.is_filesystem());
+    assert_eq!(dataset.name(), name);
+    assert_eq!(dataset.guid(), filesystem.guid());
+    assert_eq!(dataset.pool(), name.split('/').next().unwrap());
+
+    let dataset = Zfs::open(&snapshot)?;
+    assert!(dataset.is_snapshot());
+    assert_eq!(dataset.parent(), Some(name.clone()));
+
+    let snapshots = Zfs::list_from(&name).snapshots().get_collection();
+    assert_eq!(snapshots.len(), 1);
+    assert_eq!(snapshots[0].name(), snapshot);
+
+    filesystem.destroy_recursive()?;
+    Ok(())
+}
+
+#[test]
+fn extended_properties_filesystem() -> anyhow::Result<()> {
+    let namespace = TestNamespace::unique();
+    let name = namespace.unique_name();
+    let mut filesystem = Zfs::filesystem()
+        .quota(64 * 1024 * 1024)
+        .recordsize(64 * 1024)
+        .xattr(property::Xattr::Sa)
+        .acltype(property::AclType::Posix)
+        .sync(property::SyncMode::Always)
+        .logbias(property::LogBias::Throughput)
+        .primarycache(property::Cache::Metadata)
+        .copies(2)
+        .snapdir(property::SnapDir::Visible)
+        .create(&name)?;
+    assert_eq!(filesystem.quota(), "64M".parse()?);
+    assert_eq!(filesystem.recordsize().as_u64(), 64 * 1024);
+    assert_eq!(filesystem.xattr()?, property::Xattr::Sa);
+    assert_eq!(filesystem.acltype()?, property::AclType::Posix);
+    assert_eq!(filesystem.sync()?, property::SyncMode::Always);
+    assert_eq!(filesystem.logbias()?, property::LogBias::Throughput);
+    assert_eq!(filesystem.primarycache()?, property::Cache::Metadata);
+    assert_eq!(filesystem.copies(), 2);
+    assert_eq!(filesystem.snapdir()?, property::SnapDir::Visible);
+    assert_eq!(filesystem.keystatus()?, property::KeyStatus::None);
+    filesystem
+        .set()
+        .dedup(property::Dedup::Sha256Verify)
+        .redundant_metadata(property::RedundantMetadata::Most)
+        .dnodesize(property::DnodeSize::Auto)
+        .sharenfs("off")
+        .commit()?;
+    assert_eq!(filesystem.dedup()?, property::Dedup::Sha256Verify);
+    assert_eq!(
+        filesystem.redundant_metadata()?,
+        property::RedundantMetadata::Most
+    );
+    assert_eq!(filesystem.dnodesize()?, property::DnodeSize::Auto);
+    assert_eq!(filesystem.sharenfs(), "off");
+    filesystem.destroy()?;
+    Ok(())
+}
+
+#[test]
+fn property_registry() {
+    let registry = property::registry();
+    let compression = registry.get("compression").unwrap();
+    assert_eq!(compression.r#type(), property::PropType::Index);
+    assert!(compression.valid_for(property::DatasetType::Filesystem));
+    assert!(compression.inheritable());
+    assert_eq!(compression.default_value(), "off");
+    assert!(registry.get("used").unwrap().readonly());
+    assert!(registry.get("volblocksize").unwrap().setonce());
+    assert!(registry
+        .for_type(property::DatasetType::Volume)
+        .any(|info| info.name() == "volsize"));
+
+    let filesystem = property::DatasetType::Filesystem;
+    assert!(registry.validate("compression", "lz4", filesystem).is_ok());
+    assert!(registry.validate("quota", "10G", filesystem).is_ok());
+    assert!(registry
+        .validate("org.example:tag", "x", filesystem)
+        .is_ok());
+    assert_eq!(
+        registry.validate("compression", "bogus", filesystem),
+        Err(property::InvalidProperty::InvalidPropertyValue(
+            "compression".to_string(),
+            "bogus".to_string()
+        ))
+    );
+    assert_eq!(
+        registry.validate("used", "1", filesystem),
+        Err(property::InvalidProperty::ReadOnly("used".to_string()))
+    );
+    assert!(matches!(
+        registry.validate("volsize", "1G", filesystem),
+        Err(property::InvalidProperty::NotApplicable(..))
+    ));
+    assert!(matches!(
+        registry.validate("nosuchprop", "1", filesystem),
+        Err(property::InvalidProperty::NoSuchProperty(_))
+    ));
+}
+
+#[test]
+fn invalid_properties_rejected_before_create() {
+    let namespace = TestNamespace::unique();
+    let name = namespace.unique_name();
+
+    let err = Zfs::filesystem()
+        .property("nosuchprop", "1")
+        .create(&name)
+        .unwrap_err();
+    assert_eq!(
+        err,
+        property::InvalidProperty::NoSuchProperty("nosuchprop".to_string()).into()
+    );
+
+    let err = Zfs::filesystem()
+        .property("used", "1")
+        .create(&name)
+        .unwrap_err();
+    assert_eq!(
+        err,
+        property::InvalidProperty::ReadOnly("used".to_string()).into()
+    );
+
+    let err = Zfs::filesystem()
+        .property("volsize", "1G")
+        .create(&name)
+        .unwrap_err();
+    assert!(matches!(
+        err,
+        DatasetError::InvalidProperty(property::InvalidProperty::NotApplicable(..))
+    ));
+
+    let err = Zfs::filesystem().copies(4).create(&name).unwrap_err();
+    assert!(matches!(
+        err,
+        DatasetError::InvalidProperty(property::InvalidProperty::OutOfRange(..))
+    ));
+
+    let err = Zfs::volume()
+        .blocksize(3000_u64)
+        .create(&name, 1_u64 << 20)
+        .unwrap_err();
+    assert_eq!(
+        err,
+        property::InvalidProperty::NotPowerOfTwo("volblocksize".to_string(), 3000).into()
+    );
+
+    assert!(Zfs::get_filesystem(&name).is_err());
+}
+
+#[test]
+fn snapshot_builder_recursive_with_exclusions() -> anyhow::Result<()> {
+    let namespace = TestNamespace::unique();
+    let root = namespace.unique_name();
+    Zfs::filesystem().create(&root)?;
+    for child in ["a", "a/deep", "b", "skip"] {
+        Zfs::filesystem().create(format!("{root}/{child}"))?;
+    }
+
+    let snapshots = Zfs::snapshot()
+        .depth(1)
+        .exclude(format!("{root}/skip"))
+        .user_property("org.example:tag", "nightly")
+        .create(format!("{root}@snap"))?;
+    let names = snapshots.iter().map(|s| s.name()).collect::<Vec<_>>();
+    assert_eq!(
+        names,
+        [
+            format!("{root}@snap"),
+            format!("{root}/a@snap"),
+            format!("{root}/b@snap")
+        ]
+    );
+    assert_eq!(
+        snapshots[0].user_properties()["org.example:tag"].0,
+        "nightly"
+    );
+
+    let err = Zfs::snapshot()
+        .user_property("notuser", "x")
+        .create(format!("{root}@bad"))
+        .unwrap_err();
+    assert_eq!(
+        err,
+        property::InvalidProperty::InvalidUserProperty("notuser".to_string()).into()
+    );
+
+    let err = Zfs::snapshot()
+        .recursive()
+        .exclude(format!("{root}*"))
+        .create(format!("{root}@excluded"))
+        .unwrap_err();
+    assert_eq!(err, DatasetError::excluded_dataset(&root));
+
+    for snapshot in snapshots {
+        snapshot.destroy()?;
+    }
+    for child in ["a/deep", "a", "b", "skip"] {
+        Zfs::destroy_dataset(format!("{root}/{child}"))?;
+    }
+    Zfs::destroy_dataset(&root)?;
+    Ok(())
+}
@@ -0,0 +1,39 @@
+use std::time::Duration;
+
+use razor_test::TestNamespace;
+use razor_zfs as zfs;
+
+use zfs::Transfer;
+use zfs::Zfs;
+
+#[test]
+fn send_receive_with_progress() -> anyhow::Result<()> {
+    let namespace = TestNamespace::unique();
+    let name = namespace.unique_name();
+    Zfs::filesystem().create(&name)?;
+    let snapshot = format!("{name}@progress");
+    Zfs::snapshot().create(&snapshot)?;
+
+    let mut stream = Vec::new();
+    let mut reports = Vec::new();
+    let sent = Transfer::new()
+        .interval(Duration::ZERO)
+        .progress(|progress| reports.push(*progress))
+        .send(&snapshot, None::<&str>, &mut stream)?;
+    assert_eq!(sent, stream.len() as u64);
+    let last = reports.last().expect("final progress report");
+    assert!(last.done);
+    assert_eq!(last.bytes, sent);
+    assert!(last.total.is_some());
+
+    let target = format!("{}@progress", namespace.unique_name());
+    let received = Transfer::new().limit(1024 * 1024 * 1024).receive(
+        &target,
+        None::<&str>,
+        false,
+        &stream[..],
+    )?;
+    assert_eq!(received, sent);
+    assert!(Zfs::dataset_exists(&target));
+    Ok(())
+}
@@ -0,0 +1,290 @@
+use std::cell::RefCell;
+use std::io;
+use std::rc::Rc;
+
+use razor_zfs as zfs;
+
+use zfs::stream::{self, ByteOrder, Features, Header, RecordType, StreamError, StreamReader};
+
+const FULL: &[u8] = include_bytes!("fixtures/full.zstream");
+const FULL_BE: &[u8] = include_bytes!("fixtures/full-be.zstream");
+const REPLICATE: &[u8] = include_bytes!("fixtures/replicate.zstream");
+const CORRUPTED: &[u8] = include_bytes!("fixtures/corrupted.zstream");
+const RAW: &[u8] = include_bytes!("fixtures/raw.zstream");
+const REDACTED: &[u8] = include_bytes!("fixtures/redacted.zstream");
+const DEDUP: &[u8] = include_bytes!("fixtures/dedup.zstream");
+
+#[test]
+fn parse_full_stream() -> anyhow::Result<()> {
+    let records = StreamReader::new(FULL).collect::<Result<Vec<_>, _>>()?;
+    let types = records
+        .iter()
+        .map(|record| record.record_type())
+        .collect::<Vec<_>>();
+    assert_eq!(
+        types,
+        [
+            RecordType::Begin,
+            RecordType::FreeObjects,
+            RecordType::Object,
+            RecordType::Write,
+            RecordType::Write,
+            RecordType::Free,
+            RecordType::WriteEmbedded,
+            RecordType::Spill,
+            RecordType::FreeObjects,
+            RecordType::End,
+        ]
+    );
+
+    let begin = match records[0].header() {
+        Header::Begin(begin) => begin,
+        _ => panic!("BEGIN record expected"),
+    };
+    assert_eq!(begin.magic, stream::BACKUP_MAGIC);
+    assert_eq!(begin.toname, "rpool/test@snap");
+    assert_eq!(begin.toguid, 0x5d1c2a6f9e03b471);
+    assert!(!begin.is_compound());
+    assert!(begin.features().contains(Features::EMBED_DATA));
+    assert!(begin.features().contains(Features::LARGE_BLOCKS));
+    assert!(begin.features().contains(Features::SA_SPILL));
+    assert!(!begin.features().contains(Features::RAW));
+
+    let object = match records[2].header() {
+        Header::Object(object) => object,
+        _ => panic!("OBJECT record expected"),
+    };
+    assert_eq!(object.object, 1);
+    assert_eq!(object.blksz, 131072);
+    assert_eq!(records[2].payload().len(), 168);
+
+    let write = match records[4].header() {
+        Header::Write(write) => write,
+        _ => panic!("WRITE record expected"),
+    };
+    assert_eq!(write.offset, 131072);
+    assert_eq!(write.logical_size, 131072);
+    assert_eq!(write.compressed_size, 1024);
+    assert_eq!(records[4].payload().len(), 1024);
+
+    let embedded = match records[6].header() {
+        Header::WriteEmbedded(embedded) => embedded,
+        _ => panic!("WRITE_EMBEDDED record expected"),
+    };
+    assert_eq!(embedded.psize, 20);
+    assert_eq!(records[6].payload().len(), 24);
+
+    let end = records.last().unwrap();
+    let drr_end = match end.header() {
+        Header::End(drr_end) => drr_end,
+        _ => panic!("END record expected"),
+    };
+    assert_eq!(drr_end.toguid, 0x5d1c2a6f9e03b471);
+    assert!(!drr_end.checksum.is_zero());
+    assert_eq!(end.offset() + end.size(), FULL.len() as u64);
+    Ok(())
+}
+
+#[test]
+fn big_endian_stream() -> anyhow::Result<()> {
+    let mut reader = StreamReader::new(FULL_BE);
+    let be = reader.by_ref().collect::<Result<Vec<_>, _>>()?;
+    assert_eq!(reader.byte_order(), Some(ByteOrder::Big));
+    let le = StreamReader::new(FULL).collect::<Result<Vec<_>, _>>()?;
+    let be = be.iter().map(|record| record.header()).collect::<Vec<_>>();
+    let le = le.iter().map(|record| record.header()).collect::<Vec<_>>();
+    assert_eq!(be.len(), le.len());
+    // Headers are the same, but for the END record checksum
+    assert_eq!(be[..be.len() - 1], le[..le.len() - 1]);
+    Ok(())
+}
+
+#[test]
+fn statistics() -> anyhow::Result<()> {
+    let stats = stream::dump(FULL)?;
+    assert_eq!(stats.records(), 10);
+    assert_eq!(stats.get(RecordType::Write).count, 2);
+    assert_eq!(stats.get(RecordType::Write).payload, 4096 + 1024);
+    assert_eq!(stats.get(RecordType::FreeObjects).count, 2);
+    assert_eq!(stats.get(RecordType::Spill).count, 1);
+    assert_eq!(stats.length(), FULL.len() as u64);
+    assert!(stats.to_string().contains("Total DRR_WRITE records = 2"));
+    Ok(())
+}
+
+#[test]
+fn replication_stream() -> anyhow::Result<()> {
+    let records = StreamReader::new(REPLICATE).collect::<Result<Vec<_>, _>>()?;
+    let begin = match records[0].header() {
+        Header::Begin(begin) => begin,
+        _ => panic!("BEGIN record expected"),
+    };
+    assert!(begin.is_compound());
+
+    let nvlist = records[0].nvlist()?.expect("compound stream payload");
+    let tosnap = nvlist.lookup_nvpair("tosnap")?.expect("tosnap");
+    assert_eq!(tosnap.string(), "snap");
+    assert!(nvlist.lookup_nvpair("fss")?.is_some());
+
+    let ends = records
+        .iter()
+        .filter(|record| record.record_type() == RecordType::End)
+        .count();
+    assert_eq!(ends, 3);
+    Ok(())
+}
+
+#[test]
+fn feature_streams() -> anyhow::Result<()> {
+    for (fixture, feature, record_type) in [
+        (RAW, Features::RAW, RecordType::ObjectRange),
+        (REDACTED, Features::REDACTED, RecordType::Redact),
+        (DEDUP, Features::DEDUP, RecordType::WriteByRef),
+    ] {
+        let records = StreamReader::new(fixture).collect::<Result<Vec<_>, _>>()?;
+        let begin = match records[0].header() {
+            Header::Begin(begin) => begin,
+            _ => panic!("BEGIN record expected"),
+        };
+        assert!(begin.features().contains(feature));
+
+        let stats = stream::verify(fixture)?;
+        assert_eq!(stats.get(record_type).count, 1);
+    }
+    Ok(())
+}
+
+#[test]
+fn corrupted_stream() {
+    let error = stream::dump(CORRUPTED).unwrap_err();
+    assert!(matches!(
+        error,
+        StreamError::ChecksumMismatch {
+            record_type: RecordType::Write,
+            ..
+        }
+    ));
+
+    // Still readable with verification disabled
+    let records = StreamReader::new(CORRUPTED)
+        .verify(false)
+        .collect::<Result<Vec<_>, _>>()
+        .unwrap();
+    assert_eq!(records.len(), 10);
+}
+
+#[test]
+fn truncated_stream() {
+    let error = stream::dump(&FULL[..FULL.len() - 100]).unwrap_err();
+    assert!(matches!(error, StreamError::Truncated(_)));
+
+    let error = stream::dump(&FULL[..2000]).unwrap_err();
+    assert!(matches!(error, StreamError::Truncated(_)));
+}
+
+#[test]
+fn invalid_magic() {
+    let error = stream::dump(&FULL[312..]).unwrap_err();
+    assert!(matches!(error, StreamError::InvalidMagic(_)));
+}
+
+#[test]
+fn verify_stream() -> anyhow::Result<()> {
+    let stats = stream::verify(FULL)?;
+    assert_eq!(stats.length(), FULL.len() as u64);
+    stream::verify(REPLICATE)?;
+
+    // Cut exactly at the record boundary, before END
+    let error = stream::verify(&FULL[..FULL.len() - stream::RECORD_SIZE]).unwrap_err();
+    assert!(matches!(error, StreamError::Truncated(_)));
+
+    let error = stream::verify(CORRUPTED).unwrap_err();
+    assert!(matches!(error, StreamError::ChecksumMismatch { .. }));
+    Ok(())
+}
+
+#[test]
+fn verify_stream_missing_end() -> anyhow::Result<()> {
+    // Two copies of the full stream, the first one without its END record
+    let records = StreamReader::new(FULL).collect::<Result<Vec<_>, _>>()?;
+    let mut output = Vec::new();
+    let mut writer = stream::StreamWriter::new(&mut output);
+    let truncated = records
+        .iter()
+        .filter(|record| record.record_type() != RecordType::End);
+    for record in truncated.chain(&records) {
+        writer.write_record(record)?;
+    }
+
+    let error = stream::verify(&output[..]).unwrap_err();
+    assert!(matches!(
+        error,
+        StreamError::UnexpectedRecord {
+            record_type: RecordType::Begin,
+            ..
+        }
+    ));
+    Ok(())
+}
+
+#[test]
+fn split_replication_stream() -> anyhow::Result<()> {
+    let mut outputs = Vec::new();
+    let substreams = stream::split(REPLICATE, |_begin| {
+        let output = Output::default();
+        outputs.push(output.clone());
+        Ok(output)
+    })?;
+    assert_eq!(substreams.len(), 1);
+    assert_eq!(substreams[0].toname, "rpool/test@snap");
+    assert!(!substreams[0].is_compound());
+
+    let substream = outputs[0].0.borrow();
+    let stats = stream::verify(&substream[..])?;
+    assert_eq!(stats.get(RecordType::Begin).count, 1);
+    assert_eq!(stats.get(RecordType::End).count, 1);
+    assert_eq!(stats.get(RecordType::Write).count, 1);
+
+    // Plain stream is passed through intact
+    let output = Output::default();
+    stream::split(FULL, |_begin| Ok(output.clone()))?;
+    assert_eq!(*output.0.borrow(), FULL);
+    Ok(())
+}
+
+#[test]
+fn retarget_stream() -> anyhow::Result<()> {
+    let mut output = Vec::new();
+    let written = stream::retarget(FULL, &mut output, "tank/copy@snap", Some(42))?;
+    assert_eq!(written, FULL.len() as u64);
+
+    let records = StreamReader::new(&output[..]).collect::<Result<Vec<_>, _>>()?;
+    let begin = match records[0].header() {
+        Header::Begin(begin) => begin,
+        _ => panic!("BEGIN record expected"),
+    };
+    assert_eq!(begin.toname, "tank/copy@snap");
+    assert_eq!(begin.fromguid, 42);
+    stream::verify(&output[..])?;
+
+    let mut output = Vec::new();
+    let error = stream::retarget(REPLICATE, &mut output, "tank/copy@snap", None).unwrap_err();
+    assert!(matches!(error, StreamError::Compound));
+
+    let error = stream::retarget(FULL, std::io::sink(), "x".repeat(300), None).unwrap_err();
+    assert!(matches!(error, StreamError::InvalidName(_)));
+    Ok(())
+}
+
+#[derive(Clone, Default)]
+struct Output(Rc<RefCell<Vec<u8>>>);
+
+impl io::Write for Output {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.0.borrow_mut().write(buf)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
@@ -0,0 +1,36 @@
+use std::time::{Duration, SystemTime};
+
+use razor_test::TestNamespace;
+use razor_zfs as zfs;
+
+use zfs::zcp;
+use zfs::Zfs;
+
+#[test]
+fn snapshot_and_prune() -> anyhow::Result<()> {
+    let namespace = TestNamespace::unique();
+    let name = namespace.unique_name();
+    let filesystem = Zfs::filesystem().create(&name)?;
+    let snapshots = [format!("{name}@zcp1"), format!("{name}@zcp2")];
+    zcp::snapshot(&snapshots)?;
+    assert!(snapshots.iter().all(Zfs::dataset_exists));
+
+    let before = SystemTime::now() + Duration::from_secs(60);
+    let mut destroyed = zcp::destroy_snapshots_before(filesystem.name(), before, false)?;
+    destroyed.sort();
+    assert_eq!(destroyed, snapshots);
+    assert!(!snapshots.iter().any(Zfs::dataset_exists));
+    Ok(())
+}
+
+#[test]
+fn snapshot_is_atomic() -> anyhow::Result<()> {
+    let namespace = TestNamespace::unique();
+    let name = namespace.unique_name();
+    Zfs::filesystem().create(&name)?;
+    let missing = namespace.unique_name();
+    let snapshots = [format!("{name}@zcp"), format!("{missing}@zcp")];
+    zcp::snapshot(&snapshots).unwrap_err();
+    assert!(!Zfs::dataset_exists(&snapshots[0]));
+    Ok(())
+}
@@ -0,0 +1,383 @@
+use std::fs;
+use std::path::PathBuf;
+
+use razor_test::TestNamespace;
+use razor_zfs as zfs;
+
+use zfs::zpool::event::Sysevent;
+use zfs::zpool::property::{self, Health};
+use zfs::zpool::scan::{ScanFunction, ScanState};
+use zfs::zpool::tree::{VdevAux, VdevState};
+use zfs::zpool::{
+    Events, HistoryEvent, ImportOptions, ImportSearch, PoolState, Vdev, VdevSpec, ZfsEvent,
+};
+use zfs::{DatasetError, Zfs, Zpool};
+
+const DEVICE_SIZE: u64 = 128 * 1024 * 1024;
+
+#[test]
+fn get_pool_properties() -> anyhow::Result<()> {
+    let namespace = TestNamespace::unique();
+    let pool = Zpool::get(namespace.pool())?;
+    assert_eq!(pool.name(), namespace.pool());
+    assert_eq!(pool.health()?, Health::Online);
+    assert!(pool.size() > 0);
+    assert!(pool.allocated() > 0);
+    assert!(pool.capacity() <= 100);
+    assert!(pool.dedupratio() >= 100);
+    assert_ne!(pool.guid(), 0);
+    Ok(())
+}
+
+#[test]
+fn list_pools() -> anyhow::Result<()> {
+    let namespace = TestNamespace::unique();
+    let pools = Zpool::list()?;
+    assert!(pools.iter().any(|pool| pool.name() == namespace.pool()));
+    Ok(())
+}
+
+#[test]
+fn missing_pool() {
+    assert!(Zpool::get("no-such-pool").is_err());
+}
+
+#[test]
+fn create_mirror_with_classes() -> anyhow::Result<()> {
+    let devices = Devices::new("classes", 8)?;
+    let spec = VdevSpec::new()
+        .mirror(&devices[0..2])
+        .special(Vdev::mirror(&devices[2..4]))
+        .dedup(Vdev::device(&devices[4]))
+        .log(Vdev::device(&devices[5]))
+        .cache(&devices[6])
+        .spare(&devices[7]);
+    assert_eq!(spec.devices().count(), 8);
+
+    let mut pool_props = property::Properties::new();
+    pool_props.ashift(12).autotrim(property::OnOff::On);
+    let mut fs_props = zfs::zfs::property::Properties::new();
+    fs_props.mountpoint("none");
+
+    let pool = Zpool::create(&devices.name, &spec, pool_props, fs_props)?;
+    assert_eq!(pool.name(), devices.name);
+    assert_eq!(pool.health()?, Health::Online);
+    assert_eq!(*pool.ashift()?, 12);
+    assert_eq!(pool.autotrim()?, property::OnOff::On);
+    pool.destroy()?;
+    Ok(())
+}
+
+#[test]
+fn create_raidz_and_draid() -> anyhow::Result<()> {
+    let devices = Devices::new("raidz", 4)?;
+    let spec = VdevSpec::new().raidz2(&devices[..]);
+    let pool = Zpool::create(&devices.name, &spec, Default::default(), Default::default())?;
+    assert_eq!(pool.health()?, Health::Online);
+    pool.destroy()?;
+
+    let devices = Devices::new("draid", 5)?;
+    let spec = VdevSpec::new().draid(1, 1, &devices[..]);
+    let pool = Zpool::create(&devices.name, &spec, Default::default(), Default::default())?;
+    assert_eq!(pool.health()?, Health::Online);
+    pool.destroy()?;
+    Ok(())
+}
+
+#[test]
+fn invalid_vdev_spec() -> anyhow::Result<()> {
+    let devices = Devices::new("invalid", 2)?;
+    let invalid = [
+        VdevSpec::new(),
+        VdevSpec::new().mirror(&devices[..1]),
+        VdevSpec::new().raidz3(&devices[..]),
+        VdevSpec::new().draid(1, 1, &devices[..]),
+        VdevSpec::new().device("relative/path"),
+        VdevSpec::new()
+            .device(&devices[0])
+            .log(Vdev::raidz1(&devices[..])),
+    ];
+    for spec in invalid {
+        let error = spec.to_nvlist().unwrap_err();
+        assert!(
+            matches!(error, DatasetError::InvalidVdevSpec(_)),
+            "{spec:?}"
+        );
+    }
+    Ok(())
+}
+
+#[test]
+fn vdev_tree_stats() -> anyhow::Result<()> {
+    let devices = Devices::new("stats", 2)?;
+    let spec = VdevSpec::new().mirror(&devices[..]);
+    let mut fs_props = zfs::zfs::property::Properties::new();
+    fs_props.mountpoint("none");
+
+    let mut pool = Zpool::create(&devices.name, &spec, property::Properties::new(), fs_props)?;
+    pool.refresh()?;
+    let vdevs = pool.vdevs()?;
+    assert_eq!(vdevs.root().kind, "root");
+    assert_eq!(vdevs.root().health(), Health::Online);
+    assert_eq!(vdevs.leaves().count(), 2);
+    for leaf in vdevs.leaves() {
+        assert_eq!(leaf.kind, "file");
+        assert_eq!(leaf.state, VdevState::Healthy);
+        assert_eq!(leaf.aux, VdevAux::None);
+        let stats = leaf.stats.expect("leaf vdev stats");
+        assert_eq!(stats.errors(), 0);
+        assert!(stats.space > 0);
+    }
+    assert!(vdevs.find(vdevs.root().guid).is_some());
+    pool.destroy()?;
+    Ok(())
+}
+
+#[test]
+fn scrub() -> anyhow::Result<()> {
+    let devices = Devices::new("scrub", 1)?;
+    let spec = VdevSpec::new().device(&devices[0]);
+    let mut fs_props = zfs::zfs::property::Properties::new();
+    fs_props.mountpoint("none");
+
+    let mut pool = Zpool::create(&devices.name, &spec, property::Properties::new(), fs_props)?;
+    assert!(pool.scan_status()?.is_none());
+    assert!(pool.scrub_stop().is_err());
+
+    pool.scrub_start()?;
+    let status = loop {
+        pool.refresh()?;
+        let status = pool.scan_status()?.expect("scrub status");
+        if !status.is_scanning() {
+            break status;
+        }
+        std::thread::sleep(std::time::Duration::from_millis(100));
+    };
+    assert_eq!(status.function, ScanFunction::Scrub);
+    assert_eq!(status.state, ScanState::Finished);
+    assert_eq!(status.errors, 0);
+    assert!(status.end_time >= status.start_time);
+    assert_eq!(status.time_remaining(), None);
+    pool.destroy()?;
+    Ok(())
+}
+
+#[test]
+fn manage_devices() -> anyhow::Result<()> {
+    let devices = Devices::new("devices", 6)?;
+    let path = |idx: usize| devices[idx].to_string_lossy().into_owned();
+    let spec = VdevSpec::new().mirror(&devices[0..2]);
+    let mut fs_props = zfs::zfs::property::Properties::new();
+    fs_props.mountpoint("none");
+    let mut pool = Zpool::create(&devices.name, &spec, property::Properties::new(), fs_props)?;
+
+    // Single device would lower the mirror redundancy
+    let stripe = VdevSpec::new().device(&devices[2]);
+    assert!(matches!(
+        pool.add(&stripe, false),
+        Err(DatasetError::ReducedRedundancy(_))
+    ));
+    pool.add(&VdevSpec::new().mirror(&devices[2..4]), false)?;
+
+    pool.attach(path(0), &devices[4])?;
+    pool.detach(path(4))?;
+    pool.offline(path(1), true)?;
+    assert!(matches!(
+        pool.offline(path(0), true),
+        Err(DatasetError::ReducedRedundancy(_))
+    ));
+    assert_eq!(pool.online(path(1), false)?, VdevState::Healthy);
+    pool.replace(path(3), &devices[5])?;
+    pool.clear(None)?;
+
+    pool.refresh()?;
+    let vdevs = pool.vdevs()?;
+    assert_eq!(vdevs.root().children.len(), 2);
+    assert!(vdevs
+        .leaves()
+        .any(|vdev| vdev.path.as_deref() == Some(path(5).as_str())));
+    assert!(!vdevs
+        .leaves()
+        .any(|vdev| vdev.path.as_deref() == Some(path(4).as_str())));
+
+    pool.destroy()?;
+    Ok(())
+}
+
+#[test]
+fn pool_events() -> anyhow::Result<()> {
+    let events = Events::new()?.skip_existing()?.follow(false);
+    let devices = Devices::new("events", 1)?;
+    let spec = VdevSpec::new().device(&devices[0]);
+    let mut fs_props = zfs::zfs::property::Properties::new();
+    fs_props.mountpoint("none");
+    let pool = Zpool::create(&devices.name, &spec, property::Properties::new(), fs_props)?;
+
+    let created = events
+        .collect::<Result<Vec<_>, _>>()?
+        .into_iter()
+        .any(|event| match event {
+            ZfsEvent::Sysevent(Sysevent::PoolCreate, info) => {
+                info.pool.as_deref() == Some(devices.name.as_str())
+            }
+            _ => false,
+        });
+    assert!(created);
+    pool.destroy()?;
+    Ok(())
+}
+
+#[cfg(feature = "aio")]
+#[test]
+fn pool_events_async() -> anyhow::Result<()> {
+    use tokio_stream::StreamExt;
+
+    let runtime = tokio::runtime::Builder::new_current_thread()
+        .enable_all()
+        .build()?;
+    let mut events = zfs::aio::events(Events::new()?.skip_existing()?);
+    let devices = Devices::new("aio-events", 1)?;
+    let spec = VdevSpec::new().device(&devices[0]);
+    let mut fs_props = zfs::zfs::property::Properties::new();
+    fs_props.mountpoint("none");
+    let pool = Zpool::create(&devices.name, &spec, property::Properties::new(), fs_props)?;
+
+    let created = runtime.block_on(async {
+        while let Some(event) = events.next().await {
+            if let ZfsEvent::Sysevent(Sysevent::PoolCreate, info) = event? {
+                if info.pool.as_deref() == Some(devices.name.as_str()) {
+                    return Ok(true);
+                }
+            }
+        }
+        Ok::<_, zfs::DatasetError>(false)
+    })?;
+    assert!(created);
+    pool.destroy()?;
+    Ok(())
+}
+
+#[cfg(feature = "aio")]
+#[test]
+fn dropped_event_stream_stops_reader() -> anyhow::Result<()> {
+    let events = zfs::aio::events(Events::new()?.skip_existing()?);
+    // No events are expected, the reader must still notice the stream is gone
+    let (tx, rx) = std::sync::mpsc::channel();
+    std::thread::spawn(move || {
+        events.close();
+        let _ = tx.send(());
+    });
+    rx.recv_timeout(std::time::Duration::from_secs(5))?;
+    Ok(())
+}
+
+#[test]
+fn pool_history() -> anyhow::Result<()> {
+    let devices = Devices::new("history", 1)?;
+    let spec = VdevSpec::new().device(&devices[0]);
+    let mut fs_props = zfs::zfs::property::Properties::new();
+    fs_props.mountpoint("none");
+    let pool = Zpool::create(&devices.name, &spec, property::Properties::new(), fs_props)?;
+
+    // lzc ioctls are logged with their errno
+    Zfs::snapshot().create(format!("{}@history", devices.name))?;
+
+    let commands = pool.history(false).collect::<Result<Vec<_>, _>>()?;
+    assert!(commands
+        .iter()
+        .all(|record| matches!(record.event, HistoryEvent::Command(_))));
+
+    let internal = pool.history(true).collect::<Result<Vec<_>, _>>()?;
+    assert!(internal.len() >= commands.len());
+    let create = internal
+        .iter()
+        .find(|record| matches!(&record.event, HistoryEvent::Internal { name, .. } if name == "create"))
+        .expect("pool create event");
+    assert!(create.txg.is_some());
+    assert!(create.host.is_some());
+    assert!(internal.iter().any(|record| matches!(
+        &record.event,
+        HistoryEvent::Ioctl { name, errno: Some(0) } if name == "snapshot"
+    )));
+    pool.destroy()?;
+    Ok(())
+}
+
+#[test]
+fn export_and_import() -> anyhow::Result<()> {
+    let devices = Devices::new("import", 2)?;
+    let spec = VdevSpec::new().mirror(&devices[..]);
+    let pool = Zpool::create(&devices.name, &spec, Default::default(), Default::default())?;
+    let guid = pool.guid();
+    pool.export(false)?;
+
+    let search = ImportSearch::new().dir("/var/tmp");
+    let importable = search.find(&devices.name)?;
+    assert_eq!(importable.guid(), guid);
+    assert_eq!(importable.state(), PoolState::Exported);
+    assert_eq!(importable.health(), Health::Online);
+    let leaves = importable
+        .vdevs()
+        .leaves()
+        .filter_map(|vdev| vdev.path.as_deref())
+        .collect::<Vec<_>>();
+    assert_eq!(leaves.len(), 2);
+    assert!(devices
+        .iter()
+        .all(|path| leaves.contains(&path.to_str().unwrap())));
+
+    let options = ImportOptions::new().readonly(true).altroot("/mnt");
+    let pool = importable.import(options)?;
+    assert_eq!(pool.readonly()?, property::OnOff::On);
+    assert!(matches!(pool.altroot()?, property::Altroot::Directory(root) if root == "/mnt"));
+    pool.export(false)?;
+
+    // Import by guid
+    let pool = Zpool::import(guid.to_string(), &search, ImportOptions::new())?;
+    assert_eq!(pool.name(), devices.name);
+    pool.destroy()?;
+
+    let error = search.find(&devices.name).unwrap_err();
+    assert!(matches!(error, DatasetError::PoolNotFound(_)));
+    let destroyed = ImportSearch::new()
+        .dir("/var/tmp")
+        .destroyed(true)
+        .find(&devices.name)?;
+    assert_eq!(destroyed.state(), PoolState::Destroyed);
+    Ok(())
+}
+
+// Sparse files used as vdevs, removed on drop
+struct Devices {
+    name: String,
+    paths: Vec<PathBuf>,
+}
+
+impl Devices {
+    fn new(name: &str, count: usize) -> anyhow::Result<Self> {
+        let name = format!("razor-{name}-{}", std::process::id());
+        let paths = (0..count)
+            .map(|idx| PathBuf::from(format!("/var/tmp/{name}-{idx}.img")))
+            .collect::<Vec<_>>();
+        for path in &paths {
+            fs::File::create(path)?.set_len(DEVICE_SIZE)?;
+        }
+        Ok(Self { name, paths })
+    }
+}
+
+impl std::ops::Deref for Devices {
+    type Target = [PathBuf];
+
+    fn deref(&self) -> &Self::Target {
+        &self.paths
+    }
+}
+
+impl Drop for Devices {
+    fn drop(&mut self) {
+        self.paths.iter().for_each(|path| {
+            let _ = fs::remove_file(path);
+        });
+    }
+}
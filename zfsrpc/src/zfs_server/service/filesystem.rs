@@ -3,6 +3,8 @@ use proto::filesystem_property;
 use super::*;
 
 impl proto::Filesystem {
+    // TODO: map the proto properties through razor_zfs::property::registry()
+    // once this crate builds against the current razor-zfs again
     pub(crate) fn add_property(
         fs: zfs::FilesystemBuilder,
         property: filesystem_property::Property,
@@ -5,7 +5,7 @@ impl proto::CreateSnapshotRequest {
         task::spawn_blocking(|| zfs::Zfs::snapshot().create(self.name))
             .await
             .map_err(join_to_status)?
-            .map(proto::Snapshot::from)
+            .map(|mut snapshots| proto::Snapshot::from(snapshots.remove(0)))
             .map(Response::new)
             .map_err(zfs_to_status)
     }